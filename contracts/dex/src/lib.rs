@@ -11,6 +11,14 @@ mod hydradx_dex {
     use super::*;
     use shared::non_reentrant;
 
+    const BASIS_POINTS: u128 = 10_000;
+    const DEFAULT_SWAP_FEE_BP: u32 = 30; // 0.3%
+    const MAX_SWAP_FEE_BP: u32 = 1_000; // 10%
+    const PRICE_SCALE: u128 = 10_000_000_000; // 10^10 plancks
+
+    /// Pool identifier: (token_a, token_b) in the pool's stored orientation
+    pub type PoolKey = (AccountId, AccountId);
+
     /// Simple pool structure for demonstration
     #[derive(scale::Encode, scale::Decode, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
@@ -31,14 +39,58 @@ mod hydradx_dex {
         pub amount_out: u128,
     }
 
+    #[ink(event)]
+    pub struct LiquidityAdded {
+        #[ink(topic)]
+        pub provider: AccountId,
+        pub token_a: AccountId,
+        pub token_b: AccountId,
+        pub amount_a: u128,
+        pub amount_b: u128,
+        pub shares: u128,
+    }
+
+    #[ink(event)]
+    pub struct LiquidityRemoved {
+        #[ink(topic)]
+        pub provider: AccountId,
+        pub token_a: AccountId,
+        pub token_b: AccountId,
+        pub amount_a: u128,
+        pub amount_b: u128,
+        pub shares: u128,
+    }
+
+    #[ink(event)]
+    pub struct SwapFeeUpdated {
+        pub old_fee_bp: u32,
+        pub new_fee_bp: u32,
+    }
+
+    #[ink(event)]
+    pub struct PoolRemoved {
+        #[ink(topic)]
+        pub token_a: AccountId,
+        #[ink(topic)]
+        pub token_b: AccountId,
+    }
+
     #[ink(storage)]
     pub struct HydraDxDex {
         /// Pools indexed by (token_a, token_b)
-        pools: Mapping<(AccountId, AccountId), Pool>,
+        pools: Mapping<PoolKey, Pool>,
         /// Owner for admin functions
         owner: AccountId,
         reentrancy_guard: ReentrancyGuard,
-        pool_keys: Vec<(AccountId, AccountId)>,
+        pool_keys: Vec<PoolKey>,
+        /// LP shares indexed by (pool token_a, pool token_b, provider)
+        lp_balances: Mapping<(AccountId, AccountId, AccountId), u128>,
+        /// Total LP shares per pool key
+        lp_total_supply: Mapping<PoolKey, u128>,
+        /// Swap fee in basis points, retained in the pool reserves
+        fee_bp: u32,
+        /// Swap fees collected per pool as (token_a fees, token_b fees)
+        pool_fees: Mapping<PoolKey, (u128, u128)>,
     }
 
     impl HydraDxDex {
@@ -49,10 +101,16 @@ mod hydradx_dex {
                 owner: Self::env().caller(),
                 reentrancy_guard: ReentrancyGuard::new(),
                 pool_keys: Vec::new(),
+                lp_balances: Mapping::default(),
+                lp_total_supply: Mapping::default(),
+                fee_bp: DEFAULT_SWAP_FEE_BP,
+                pool_fees: Mapping::default(),
             }
         }
 
         /// Admin: Add or update a pool (for demo/testing)
+        /// Pools with outstanding LP shares cannot be overwritten. No tokens back the
+        /// reserves set here, so such pools do not accept liquidity.
         #[ink(message)]
         pub fn set_pool(
             &mut self,
@@ -64,18 +122,315 @@ mod hydradx_dex {
             if self.env().caller() != self.owner {
                 return Err(Error::Unauthorized);
             }
-            let pool = Pool {
-                token_a,
-                token_b,
-                reserve_a,
-                reserve_b,
+            if let Ok(existing) = self.find_pool(token_a, token_b) {
+                let key: PoolKey = (existing.token_a, existing.token_b);
+                if self.lp_total_supply.get(key).unwrap_or(0) > 0 {
+                    return Err(Error::InvalidParameter);
+                }
+            }
+            // Update an existing pool in its stored orientation
+            let pool = match self.find_pool(token_a, token_b) {
+                Ok(existing) if existing.token_a == token_b => Pool {
+                    token_a: token_b,
+                    token_b: token_a,
+                    reserve_a: reserve_b,
+                    reserve_b: reserve_a,
+                },
+                Ok(_) => Pool {
+                    token_a,
+                    token_b,
+                    reserve_a,
+                    reserve_b,
+                },
+                Err(_) => {
+                    self.pool_keys.push((token_a, token_b));
+                    Pool {
+                        token_a,
+                        token_b,
+                        reserve_a,
+                        reserve_b,
+                    }
+                }
             };
-            self.pools.insert((token_a, token_b), &pool);
-            self.pool_keys.push((token_a, token_b));
+            self.pools.insert((pool.token_a, pool.token_b), &pool);
             Ok(())
         }
 
+        /// Admin: Remove the pool between two tokens
+        /// Pools with outstanding LP shares cannot be removed.
+        #[ink(message)]
+        pub fn remove_pool(&mut self, token_a: AccountId, token_b: AccountId) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            let pool = self.find_pool(token_a, token_b)?;
+            let key: PoolKey = (pool.token_a, pool.token_b);
+            if self.lp_total_supply.get(key).unwrap_or(0) > 0 {
+                return Err(Error::InvalidParameter);
+            }
+            self.pools.remove(key);
+            self.pool_fees.remove(key);
+            self.lp_total_supply.remove(key);
+            self.pool_keys.retain(|existing| *existing != key);
+            self.env().emit_event(PoolRemoved {
+                token_a: key.0,
+                token_b: key.1,
+            });
+            Ok(())
+        }
+
+        /// Get the pool between two tokens in either orientation
+        #[ink(message)]
+        pub fn get_pool(&self, token_a: AccountId, token_b: AccountId) -> Option<Pool> {
+            self.find_pool(token_a, token_b).ok()
+        }
+
+        /// Get every pool as (token_a, token_b, reserve_a, reserve_b)
+        #[ink(message)]
+        pub fn get_all_pools(&self) -> Vec<(AccountId, AccountId, u128, u128)> {
+            self.pool_keys
+                .iter()
+                .filter_map(|key| self.pools.get(*key))
+                .map(|pool| (pool.token_a, pool.token_b, pool.reserve_a, pool.reserve_b))
+                .collect()
+        }
+
+        /// Admin: Set the swap fee in basis points (max 1000 = 10%)
+        #[ink(message)]
+        pub fn set_swap_fee(&mut self, fee_bp: u32) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            if fee_bp > MAX_SWAP_FEE_BP {
+                return Err(Error::InvalidParameter);
+            }
+            let old_fee_bp = self.fee_bp;
+            self.fee_bp = fee_bp;
+            self.env().emit_event(SwapFeeUpdated {
+                old_fee_bp,
+                new_fee_bp: fee_bp,
+            });
+            Ok(())
+        }
+
+        /// Get the swap fee in basis points
+        #[ink(message)]
+        pub fn get_swap_fee(&self) -> u32 {
+            self.fee_bp
+        }
+
+        /// Get swap fees collected by the pool for a pair
+        /// Returns (fees in token_a, fees in token_b) in argument order.
+        #[ink(message)]
+        pub fn get_pool_fees(&self, token_a: AccountId, token_b: AccountId) -> (u128, u128) {
+            match self.find_pool(token_a, token_b) {
+                Ok(pool) => {
+                    let (fees_a, fees_b) = self
+                        .pool_fees
+                        .get((pool.token_a, pool.token_b))
+                        .unwrap_or((0, 0));
+                    if pool.token_a == token_a {
+                        (fees_a, fees_b)
+                    } else {
+                        (fees_b, fees_a)
+                    }
+                }
+                Err(_) => (0, 0),
+            }
+        }
+
+        /// Deposit both tokens of a pair and receive LP shares
+        /// The first deposit mints sqrt(amount_a * amount_b) shares; later deposits
+        /// mint in proportion to the smaller relative contribution and only take
+        /// the amounts matching the pool ratio (rounded up), leaving the excess.
+        /// Returns the minted shares; `LiquidityAdded` reports the amounts taken.
+        /// Pools seeded through `set_pool` without LP shares are rejected.
+        #[ink(message)]
+        pub fn add_liquidity(
+            &mut self,
+            token_a: AccountId,
+            token_b: AccountId,
+            amount_a: u128,
+            amount_b: u128,
+        ) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                if token_a == token_b || amount_a == 0 || amount_b == 0 {
+                    return Err(Error::InvalidParameter);
+                }
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                let mut pool = match self.find_pool(token_a, token_b) {
+                    Ok(pool) => pool,
+                    Err(_) => {
+                        self.pool_keys.push((token_a, token_b));
+                        Pool {
+                            token_a,
+                            token_b,
+                            reserve_a: 0,
+                            reserve_b: 0,
+                        }
+                    }
+                };
+                let key: PoolKey = (pool.token_a, pool.token_b);
+                // Amounts in pool orientation
+                let (deposit_a, deposit_b) = if pool.token_a == token_a {
+                    (amount_a, amount_b)
+                } else {
+                    (amount_b, amount_a)
+                };
+
+                let total_shares = self.lp_total_supply.get(key).unwrap_or(0);
+                if total_shares == 0 && (pool.reserve_a > 0 || pool.reserve_b > 0) {
+                    // Reserves from set_pool are not backed by tokens; shares against
+                    // them would let LP deposits be withdrawn by whoever held them
+                    return Err(Error::InvalidParameter);
+                }
+
+                let shares = if total_shares == 0 {
+                    Self::isqrt(
+                        deposit_a
+                            .checked_mul(deposit_b)
+                            .ok_or(Error::InvalidParameter)?,
+                    )
+                } else {
+                    let shares_a = deposit_a.saturating_mul(total_shares) / pool.reserve_a;
+                    let shares_b = deposit_b.saturating_mul(total_shares) / pool.reserve_b;
+                    shares_a.min(shares_b)
+                };
+                if shares == 0 {
+                    return Err(Error::InvalidParameter);
+                }
+
+                let (deposit_a, deposit_b) = if total_shares == 0 {
+                    (deposit_a, deposit_b)
+                } else {
+                    (
+                        Self::deposit_for_shares(shares, pool.reserve_a, total_shares)?,
+                        Self::deposit_for_shares(shares, pool.reserve_b, total_shares)?,
+                    )
+                };
+                // Back to argument order
+                let (amount_a, amount_b) = if pool.token_a == token_a {
+                    (deposit_a, deposit_b)
+                } else {
+                    (deposit_b, deposit_a)
+                };
+
+                self.call_token_transfer_from(token_a, caller, contract, amount_a)?;
+                self.call_token_transfer_from(token_b, caller, contract, amount_b)?;
+
+                pool.reserve_a = pool.reserve_a.saturating_add(deposit_a);
+                pool.reserve_b = pool.reserve_b.saturating_add(deposit_b);
+                self.pools.insert(key, &pool);
+
+                let balance = self.lp_balances.get((key.0, key.1, caller)).unwrap_or(0);
+                self.lp_balances
+                    .insert((key.0, key.1, caller), &balance.saturating_add(shares));
+                self.lp_total_supply
+                    .insert(key, &total_shares.saturating_add(shares));
+
+                self.env().emit_event(LiquidityAdded {
+                    provider: caller,
+                    token_a,
+                    token_b,
+                    amount_a,
+                    amount_b,
+                    shares,
+                });
+                Ok(shares)
+            })
+        }
+
+        /// Burn LP shares and withdraw the pro-rata reserves
+        /// Returns (amount of token_a, amount of token_b) in argument order.
+        #[ink(message)]
+        pub fn remove_liquidity(
+            &mut self,
+            token_a: AccountId,
+            token_b: AccountId,
+            shares: u128,
+        ) -> Result<(u128, u128), Error> {
+            non_reentrant!(self, {
+                if shares == 0 {
+                    return Err(Error::InvalidParameter);
+                }
+                let caller = self.env().caller();
+
+                let mut pool = self.find_pool(token_a, token_b)?;
+                let key: PoolKey = (pool.token_a, pool.token_b);
+                let balance = self.lp_balances.get((key.0, key.1, caller)).unwrap_or(0);
+                if balance < shares {
+                    return Err(Error::InsufficientBalance);
+                }
+                let total_shares = self.lp_total_supply.get(key).unwrap_or(0);
+                if total_shares == 0 {
+                    return Err(Error::InsufficientBalance);
+                }
+
+                let out_a = pool.reserve_a.saturating_mul(shares) / total_shares;
+                let out_b = pool.reserve_b.saturating_mul(shares) / total_shares;
+
+                pool.reserve_a = pool.reserve_a.saturating_sub(out_a);
+                pool.reserve_b = pool.reserve_b.saturating_sub(out_b);
+                self.pools.insert(key, &pool);
+                self.lp_balances
+                    .insert((key.0, key.1, caller), &balance.saturating_sub(shares));
+                self.lp_total_supply
+                    .insert(key, &total_shares.saturating_sub(shares));
+
+                // Amounts in argument orientation
+                let (amount_a, amount_b) = if pool.token_a == token_a {
+                    (out_a, out_b)
+                } else {
+                    (out_b, out_a)
+                };
+                self.call_token_transfer(token_a, caller, amount_a)?;
+                self.call_token_transfer(token_b, caller, amount_b)?;
+
+                self.env().emit_event(LiquidityRemoved {
+                    provider: caller,
+                    token_a,
+                    token_b,
+                    amount_a,
+                    amount_b,
+                    shares,
+                });
+                Ok((amount_a, amount_b))
+            })
+        }
+
+        /// Get a provider's LP shares in the pool for a pair
+        #[ink(message)]
+        pub fn get_lp_balance(
+            &self,
+            token_a: AccountId,
+            token_b: AccountId,
+            provider: AccountId,
+        ) -> u128 {
+            match self.find_pool(token_a, token_b) {
+                Ok(pool) => self
+                    .lp_balances
+                    .get((pool.token_a, pool.token_b, provider))
+                    .unwrap_or(0),
+                Err(_) => 0,
+            }
+        }
+
+        /// Get total LP shares for a pair
+        #[ink(message)]
+        pub fn get_lp_total_supply(&self, token_a: AccountId, token_b: AccountId) -> u128 {
+            match self.find_pool(token_a, token_b) {
+                Ok(pool) => self
+                    .lp_total_supply
+                    .get((pool.token_a, pool.token_b))
+                    .unwrap_or(0),
+                Err(_) => 0,
+            }
+        }
+
         /// Swap tokens from one to another
+        /// `path` may route through intermediate tokens; each hop's output feeds the next.
         #[ink(message, selector = 0x0D0E0F10)]
         pub fn swap(
             &mut self,
@@ -83,53 +438,551 @@ mod hydradx_dex {
             to: AccountId,
             amount: u128,
             path: Vec<AccountId>,
+        ) -> Result<u128, Error> {
+            non_reentrant!(self, { self.execute_swap(from, to, amount, path, 0, None) })
+        }
+
+        /// Swap tokens, failing with SlippageExceeded if the output is below `min_amount_out`
+        #[ink(message, selector = 0x0D0E0F11)]
+        pub fn swap_with_min_out(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+            path: Vec<AccountId>,
+            min_amount_out: u128,
         ) -> Result<u128, Error> {
             non_reentrant!(self, {
-                if path.len() != 2 || path[0] != from || path[1] != to {
-                    return Err(Error::InvalidParameters);
-                }
-                let mut pool = self
-                    .pools
-                    .get((from, to))
-                    .or_else(|| self.pools.get((to, from)))
-                    .ok_or(Error::TokenNotFound)?;
-                let (reserve_in, reserve_out) = if pool.token_a == from {
-                    (&mut pool.reserve_a, &mut pool.reserve_b)
-                } else {
-                    (&mut pool.reserve_b, &mut pool.reserve_a)
-                };
-                if *reserve_in < amount || *reserve_in == 0 || *reserve_out == 0 {
-                    return Err(Error::InsufficientBalance);
-                }
-                // x * y = k, dy = (y * dx) / (x + dx)
-                let amount_out = (*reserve_out as u128).saturating_mul(amount)
-                    / ((*reserve_in as u128).saturating_add(amount));
-                *reserve_in = reserve_in.saturating_add(amount);
-                *reserve_out = reserve_out.saturating_sub(amount_out);
-                self.pools.insert((pool.token_a, pool.token_b), &pool);
-                self.env().emit_event(SwapExecuted {
-                    from,
-                    to,
-                    amount_in: amount,
-                    amount_out,
-                });
-                Ok(amount_out)
+                self.execute_swap(from, to, amount, path, min_amount_out, None)
+            })
+        }
+
+        /// Swap tokens with an output floor and an optional per-hop price impact cap
+        /// Fails with PriceImpactTooHigh if any hop exceeds `max_price_impact_bp`.
+        #[ink(message, selector = 0x0D0E0F12)]
+        pub fn swap_with_limits(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+            path: Vec<AccountId>,
+            min_amount_out: u128,
+            max_price_impact_bp: Option<u32>,
+        ) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.execute_swap(from, to, amount, path, min_amount_out, max_price_impact_bp)
             })
         }
 
+        /// Price impact of swapping `amount` through the direct pool, in basis points
+        /// Measured as the shortfall of the execution price (after fees) against the
+        /// spot price implied by the pre-swap reserves.
+        #[ink(message)]
+        pub fn get_price_impact_bp(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<u32, Error> {
+            self.price_impact_hop(from, to, amount)
+        }
+
+        /// Quote the output of swapping `amount` through the direct pool, without swapping
+        #[ink(message)]
+        pub fn get_amount_out(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<u128, Error> {
+            self.quote_hop(from, to, amount)
+        }
+
+        /// Quote the input needed through the direct pool to receive at least `amount_out`
+        /// Inverse of get_amount_out, including the swap fee and rounding up.
+        #[ink(message)]
+        pub fn get_amount_in(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            amount_out: u128,
+        ) -> Result<u128, Error> {
+            let pool = self.find_pool(from, to)?;
+            let (reserve_in, reserve_out) = if pool.token_a == from {
+                (pool.reserve_a, pool.reserve_b)
+            } else {
+                (pool.reserve_b, pool.reserve_a)
+            };
+            if amount_out == 0 {
+                return Ok(0);
+            }
+            if reserve_in == 0 || amount_out >= reserve_out {
+                return Err(Error::InsufficientBalance);
+            }
+            let fee_factor = BASIS_POINTS.saturating_sub(self.fee_bp as u128);
+            // dx = x * dy * 10000 / ((y - dy) * (10000 - fee)), rounded up
+            let numerator = reserve_in
+                .saturating_mul(amount_out)
+                .saturating_mul(BASIS_POINTS);
+            let denominator = reserve_out
+                .saturating_sub(amount_out)
+                .saturating_mul(fee_factor);
+            if denominator == 0 {
+                return Err(Error::InvalidParameter);
+            }
+            Ok(numerator / denominator + 1)
+        }
+
+        fn execute_swap(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+            path: Vec<AccountId>,
+            min_amount_out: u128,
+            max_price_impact_bp: Option<u32>,
+        ) -> Result<u128, Error> {
+            if path.len() < 2 || path.first() != Some(&from) || path.last() != Some(&to) {
+                return Err(Error::InvalidParameter);
+            }
+            // Check the floor against current reserves before touching any pool
+            let mut quoted = amount;
+            for hop in path.windows(2) {
+                if let Some(max_impact) = max_price_impact_bp {
+                    if self.price_impact_hop(hop[0], hop[1], quoted)? > max_impact {
+                        return Err(Error::PriceImpactTooHigh);
+                    }
+                }
+                quoted = self.quote_hop(hop[0], hop[1], quoted)?;
+            }
+            if quoted < min_amount_out {
+                return Err(Error::SlippageExceeded);
+            }
+            let mut amount_out = amount;
+            for hop in path.windows(2) {
+                amount_out = self.swap_hop(hop[0], hop[1], amount_out)?;
+            }
+            // A path reusing a pool can execute below its quote
+            if amount_out < min_amount_out {
+                return Err(Error::SlippageExceeded);
+            }
+            Ok(amount_out)
+        }
+
+        /// Integer square root (floor)
+        fn isqrt(value: u128) -> u128 {
+            if value < 2 {
+                return value;
+            }
+            let mut x = value;
+            let mut y = x.div_ceil(2);
+            while y < x {
+                x = y;
+                y = (x + value / x) / 2;
+            }
+            x
+        }
+
+        /// Reserve amount backing `shares`, rounded up in the pool's favour
+        fn deposit_for_shares(
+            shares: u128,
+            reserve: u128,
+            total_shares: u128,
+        ) -> Result<u128, Error> {
+            Ok(shares
+                .checked_mul(reserve)
+                .ok_or(Error::InvalidParameter)?
+                .div_ceil(total_shares))
+        }
+
+        fn call_token_transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), Error> {
+            ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22::transfer_from"),
+                    ))
+                    .push_arg(from)
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TransferFailed)? // Handle LangError
+                .map_err(|_| Error::TransferFailed)? // Handle contract error
+        }
+
+        fn call_token_transfer(
+            &self,
+            token: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), Error> {
+            ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22::transfer"),
+                    ))
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TransferFailed)? // Handle LangError
+                .map_err(|_| Error::TransferFailed)? // Handle contract error
+        }
+
+        /// Look up the pool between two tokens in either orientation
+        fn find_pool(&self, from: AccountId, to: AccountId) -> Result<Pool, Error> {
+            self.pools
+                .get((from, to))
+                .or_else(|| self.pools.get((to, from)))
+                .ok_or(Error::PoolNotFound)
+        }
+
+        /// Output of a single hop at current reserves
+        fn quote_hop(&self, from: AccountId, to: AccountId, amount: u128) -> Result<u128, Error> {
+            let pool = self.find_pool(from, to)?;
+            let (reserve_in, reserve_out) = if pool.token_a == from {
+                (pool.reserve_a, pool.reserve_b)
+            } else {
+                (pool.reserve_b, pool.reserve_a)
+            };
+            if reserve_in < amount || reserve_in == 0 || reserve_out == 0 {
+                return Err(Error::InsufficientBalance);
+            }
+            // x * y = k on the post-fee input, dy = (y * dx') / (x + dx')
+            // The full `amount` still enters the reserves, so the fee stays with LPs
+            let fee_factor = BASIS_POINTS.saturating_sub(self.fee_bp as u128);
+            let amount_with_fee = amount.saturating_mul(fee_factor);
+            Ok(reserve_out.saturating_mul(amount_with_fee)
+                / reserve_in
+                    .saturating_mul(BASIS_POINTS)
+                    .saturating_add(amount_with_fee))
+        }
+
+        /// Price impact in basis points of one hop against pre-swap reserves
+        fn price_impact_hop(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<u32, Error> {
+            let amount_out = self.quote_hop(from, to, amount)?;
+            let pool = self.find_pool(from, to)?;
+            let (reserve_in, reserve_out) = if pool.token_a == from {
+                (pool.reserve_a, pool.reserve_b)
+            } else {
+                (pool.reserve_b, pool.reserve_a)
+            };
+            let spot_out = amount.saturating_mul(reserve_out) / reserve_in;
+            if spot_out == 0 {
+                return Ok(0);
+            }
+            let impact = spot_out
+                .saturating_sub(amount_out)
+                .saturating_mul(BASIS_POINTS)
+                / spot_out;
+            Ok(impact.min(BASIS_POINTS) as u32)
+        }
+
+        /// Swap through the single pool between `from` and `to`
+        fn swap_hop(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<u128, Error> {
+            let amount_out = self.quote_hop(from, to, amount)?;
+            let mut pool = self.find_pool(from, to)?;
+            let (reserve_in, reserve_out) = if pool.token_a == from {
+                (&mut pool.reserve_a, &mut pool.reserve_b)
+            } else {
+                (&mut pool.reserve_b, &mut pool.reserve_a)
+            };
+            *reserve_in = reserve_in.saturating_add(amount);
+            *reserve_out = reserve_out.saturating_sub(amount_out);
+            let key: PoolKey = (pool.token_a, pool.token_b);
+            self.pools.insert(key, &pool);
+
+            let fee = amount.saturating_mul(self.fee_bp as u128) / BASIS_POINTS;
+            let (mut fees_a, mut fees_b) = self.pool_fees.get(key).unwrap_or((0, 0));
+            if pool.token_a == from {
+                fees_a = fees_a.saturating_add(fee);
+            } else {
+                fees_b = fees_b.saturating_add(fee);
+            }
+            self.pool_fees.insert(key, &(fees_a, fees_b));
+            self.env().emit_event(SwapExecuted {
+                from,
+                to,
+                amount_in: amount,
+                amount_out,
+            });
+            Ok(amount_out)
+        }
+
         /// Get token price
+        /// Returns the reserve ratio of `token` to its pair token, scaled by 10^10
+        /// (PRICE_SCALE) so sub-unit prices are preserved.
         #[ink(message, selector = 0x11121314)]
         pub fn get_token_price(&self, token: AccountId) -> Result<u128, Error> {
             for key in &self.pool_keys {
                 if let Some(pool) = self.pools.get(*key) {
                     if pool.token_a == token && pool.reserve_b > 0 {
-                        return Ok(pool.reserve_a / pool.reserve_b);
+                        return Ok(pool.reserve_a.saturating_mul(PRICE_SCALE) / pool.reserve_b);
                     } else if pool.token_b == token && pool.reserve_a > 0 {
-                        return Ok(pool.reserve_b / pool.reserve_a);
+                        return Ok(pool.reserve_b.saturating_mul(PRICE_SCALE) / pool.reserve_a);
                     }
                 }
             }
-            Err(Error::TokenNotFound)
+            Err(Error::PoolNotFound)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn token(byte: u8) -> AccountId {
+            AccountId::from([byte; 32])
+        }
+
+        #[ink::test]
+        fn swap_routes_through_intermediate_token() {
+            let mut dex = HydraDxDex::new();
+            let (a, b, c) = (token(1), token(2), token(3));
+            dex.set_pool(a, b, 1_000, 1_000).unwrap();
+            dex.set_pool(b, c, 1_000, 1_000).unwrap();
+
+            // 0.3% fee: hop 1: 1000 * 99.7 / 1099.7 = 90; hop 2: 1000 * 89.73 / 1089.73 = 82
+            assert_eq!(dex.swap(a, c, 100, vec![a, b, c]), Ok(82));
+        }
+
+        #[ink::test]
+        fn swap_with_min_out_enforces_floor() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000, 1_000).unwrap();
+
+            // 0.3% fee: 1000 * 99.7 / 1099.7 = 90
+            assert_eq!(dex.get_amount_out(a, b, 100), Ok(90));
+            assert_eq!(
+                dex.swap_with_min_out(a, b, 100, vec![a, b], 91),
+                Err(Error::SlippageExceeded)
+            );
+            assert_eq!(dex.swap_with_min_out(a, b, 100, vec![a, b], 90), Ok(90));
+        }
+
+        #[ink::test]
+        fn swap_fee_stays_in_pool_reserves() {
+            let (a, b) = (token(1), token(2));
+            let mut with_fee = HydraDxDex::new();
+            with_fee.set_pool(a, b, 1_000_000, 1_000_000).unwrap();
+            let mut fee_free = HydraDxDex::new();
+            fee_free.set_pool(a, b, 1_000_000, 1_000_000).unwrap();
+            fee_free.set_swap_fee(0).unwrap();
+
+            for _ in 0..2 {
+                let quote = with_fee.get_amount_out(a, b, 10_000).unwrap();
+                assert_eq!(with_fee.swap(a, b, 10_000, vec![a, b]), Ok(quote));
+                fee_free.swap(a, b, 10_000, vec![a, b]).unwrap();
+            }
+
+            // 0.3% of 2 * 10_000 collected in the input token
+            assert_eq!(with_fee.get_pool_fees(a, b), (60, 0));
+            assert_eq!(with_fee.get_pool_fees(b, a), (0, 60));
+            assert_eq!(fee_free.get_pool_fees(a, b), (0, 0));
+
+            let pool = with_fee.find_pool(a, b).unwrap();
+            let baseline = fee_free.find_pool(a, b).unwrap();
+            assert_eq!(pool.reserve_a, baseline.reserve_a);
+            assert!(pool.reserve_b > baseline.reserve_b);
+            assert!(pool.reserve_a * pool.reserve_b > baseline.reserve_a * baseline.reserve_b);
+        }
+
+        #[ink::test]
+        fn set_swap_fee_rejects_above_cap() {
+            let mut dex = HydraDxDex::new();
+            assert_eq!(dex.get_swap_fee(), 30);
+            assert_eq!(dex.set_swap_fee(1_001), Err(Error::InvalidParameter));
+            assert_eq!(dex.set_swap_fee(1_000), Ok(()));
+            assert_eq!(dex.get_swap_fee(), 1_000);
+        }
+
+        #[ink::test]
+        fn swap_with_limits_rejects_excess_price_impact() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000, 1_000).unwrap();
+
+            // Spot out 100, execution out 90: 1000 bp impact
+            assert_eq!(dex.get_price_impact_bp(a, b, 100), Ok(1_000));
+            assert_eq!(
+                dex.swap_with_limits(a, b, 100, vec![a, b], 0, Some(999)),
+                Err(Error::PriceImpactTooHigh)
+            );
+            assert_eq!(
+                dex.swap_with_limits(a, b, 100, vec![a, b], 0, Some(1_000)),
+                Ok(90)
+            );
+        }
+
+        #[ink::test]
+        fn get_token_price_preserves_sub_unit_ratios() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1, 1_000).unwrap();
+
+            // 1 / 1000 scaled by 10^10
+            assert_eq!(dex.get_token_price(a), Ok(10_000_000));
+            assert_eq!(dex.get_token_price(b), Ok(1_000 * PRICE_SCALE));
+        }
+
+        #[ink::test]
+        fn get_token_price_matches_small_swap_quote() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000_000, 1_000_000_000).unwrap();
+            dex.set_swap_fee(0).unwrap();
+
+            // Price of a is quoted in a per unit of b
+            let price = dex.get_token_price(a).unwrap();
+            let amount_in = 1_000_000;
+            let expected = amount_in * price / PRICE_SCALE;
+            let quoted = dex.get_amount_out(b, a, amount_in).unwrap();
+            assert_eq!(expected, 1_000);
+            // Only price impact separates the quote from the spot price
+            assert!(quoted <= expected && quoted >= expected * 99 / 100);
+        }
+
+        #[ink::test]
+        fn get_amount_in_inverts_get_amount_out() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000_000, 2_000_000).unwrap();
+
+            let amount_in = dex.get_amount_in(a, b, 50_000).unwrap();
+            assert!(dex.get_amount_out(a, b, amount_in).unwrap() >= 50_000);
+            assert!(dex.get_amount_out(a, b, amount_in - 1).unwrap() < 50_000);
+            assert_eq!(
+                dex.get_amount_in(a, b, 2_000_000),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn set_pool_updates_existing_pool_without_duplicate_key() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000, 2_000).unwrap();
+            dex.set_pool(a, b, 3_000, 4_000).unwrap();
+            dex.set_pool(b, a, 6_000, 5_000).unwrap();
+
+            assert_eq!(dex.pool_keys.len(), 1);
+            assert_eq!(dex.get_all_pools(), vec![(a, b, 5_000, 6_000)]);
+            let pool = dex.get_pool(b, a).unwrap();
+            assert_eq!((pool.token_a, pool.reserve_a), (a, 5_000));
+            assert!(dex.get_pool(a, token(3)).is_none());
+        }
+
+        #[ink::test]
+        fn remove_pool_deletes_pool_and_key() {
+            let mut dex = HydraDxDex::new();
+            let (a, b, c) = (token(1), token(2), token(3));
+            dex.set_pool(a, b, 1_000, 2_000).unwrap();
+            dex.set_pool(a, c, 1_000, 4_000).unwrap();
+
+            assert_eq!(dex.remove_pool(b, a), Ok(()));
+            assert!(dex.get_pool(a, b).is_none());
+            assert_eq!(dex.pool_keys, vec![(a, c)]);
+            // Price now comes from the remaining pool
+            assert_eq!(dex.get_token_price(c), Ok(4 * PRICE_SCALE));
+            assert_eq!(dex.get_token_price(b), Err(Error::PoolNotFound));
+            assert_eq!(dex.remove_pool(a, b), Err(Error::PoolNotFound));
+        }
+
+        #[ink::test]
+        fn add_liquidity_rejects_invalid_input() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+
+            assert_eq!(
+                dex.add_liquidity(a, b, 0, 100),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                dex.add_liquidity(a, a, 100, 100),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(dex.remove_liquidity(a, b, 10), Err(Error::PoolNotFound));
+            assert_eq!(dex.get_lp_total_supply(a, b), 0);
+        }
+
+        #[ink::test]
+        fn set_pool_rejects_pool_with_lp_shares() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000, 2_000).unwrap();
+            dex.lp_total_supply.insert((a, b), &1_000);
+
+            assert_eq!(dex.set_pool(b, a, 5, 5), Err(Error::InvalidParameter));
+            let pool = dex.get_pool(a, b).unwrap();
+            assert_eq!((pool.reserve_a, pool.reserve_b), (1_000, 2_000));
+        }
+
+        #[ink::test]
+        fn add_liquidity_rejects_unbacked_pool() {
+            let mut dex = HydraDxDex::new();
+            let (a, b) = (token(1), token(2));
+            dex.set_pool(a, b, 1_000, 2_000).unwrap();
+
+            assert_eq!(
+                dex.add_liquidity(a, b, 100, 200),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(dex.lp_total_supply.get((a, b)), None);
+        }
+
+        #[ink::test]
+        fn deposit_for_shares_matches_pool_ratio() {
+            // Pool 1_000 / 2_000 with 1_000 shares: offering (100, 500) mints 100 shares
+            assert_eq!(HydraDxDex::deposit_for_shares(100, 1_000, 1_000), Ok(100));
+            assert_eq!(HydraDxDex::deposit_for_shares(100, 2_000, 1_000), Ok(200));
+            // Rounds up so the pool is never short-changed
+            assert_eq!(HydraDxDex::deposit_for_shares(1, 1_000, 3), Ok(334));
+        }
+
+        #[ink::test]
+        fn isqrt_floors() {
+            assert_eq!(HydraDxDex::isqrt(0), 0);
+            assert_eq!(HydraDxDex::isqrt(1), 1);
+            assert_eq!(HydraDxDex::isqrt(1_000_000), 1_000);
+            assert_eq!(HydraDxDex::isqrt(15), 3);
+        }
+
+        #[ink::test]
+        fn swap_fails_when_hop_has_no_pool() {
+            let mut dex = HydraDxDex::new();
+            let (a, b, c) = (token(1), token(2), token(3));
+            dex.set_pool(a, b, 1_000, 1_000).unwrap();
+
+            assert_eq!(dex.swap(a, c, 100, vec![a, b, c]), Err(Error::PoolNotFound));
         }
     }
 }
@@ -5,8 +5,9 @@
 #[ink::contract]
 mod oracle {
     use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
-    use shared::Error;
+    use shared::{Error, Role};
 
     /// Enhanced token price data with validation metadata
     #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
@@ -23,6 +24,10 @@ mod oracle {
         pub volume_24h: u128,
         /// Last update timestamp
         pub timestamp: u64,
+        /// Reporter uncertainty in basis points (0 = not reported)
+        pub confidence_bp: u32,
+        /// Account that submitted the latest update
+        pub last_updater: AccountId,
     }
 
     /// Global validation configuration
@@ -50,18 +55,68 @@ mod oracle {
         }
     }
 
+    /// Auto-pause configuration for repeated validation failures
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct BreakerConfig {
+        /// Consecutive failures per token that pause the oracle
+        pub failure_threshold: u32,
+        /// Failures older than this window (ms) no longer count
+        pub window_ms: u64,
+    }
+
+    impl Default for BreakerConfig {
+        fn default() -> Self {
+            Self {
+                failure_threshold: 5, // 5 rejected prices
+                window_ms: 3_600_000, // within 1 hour
+            }
+        }
+    }
+
     #[ink(storage)]
     pub struct Oracle {
         /// Enhanced price data for tokens
         token_data: Mapping<AccountId, TokenPriceData>,
+        /// Recent price observations per token: token -> [(timestamp, price)], oldest first
+        price_history: Mapping<AccountId, Vec<(u64, u128)>>,
+        /// Latest price submitted by each source: (token, updater) -> (price, timestamp)
+        submissions: Mapping<(AccountId, AccountId), (u128, u64)>,
         /// Authorized price updaters (in addition to owner)
         authorized_updaters: Mapping<AccountId, bool>,
+        /// Authorized updaters list (for median aggregation)
+        updater_list: Vec<AccountId>,
+        /// Sources required for median pricing and finalize_price (0 = median disabled)
+        min_sources: u32,
         /// Global validation configuration
         validation_config: ValidationConfig,
+        /// Per-token validation rules that replace the global configuration
+        token_config_overrides: Mapping<AccountId, ValidationConfig>,
+        /// Required refresh interval per token (ms); defaults to the staleness threshold
+        heartbeats: Mapping<AccountId, u64>,
+        /// Price precision per token when it differs from plancks
+        price_decimals: Mapping<AccountId, u8>,
         /// Contract owner
         owner: AccountId,
         /// Emergency pause flag
         paused: bool,
+        /// Reference price per token for drift detection: token -> (price, timestamp, epoch)
+        price_anchors: Mapping<AccountId, (u128, u64, u32)>,
+        /// Anchors from an older epoch are ignored (bumped on resume_updates)
+        anchor_epoch: u32,
+        /// Maximum cumulative deviation from the anchor before auto-pausing (basis points)
+        circuit_breaker_bp: u32,
+        /// Failure-count breaker configuration
+        breaker_config: BreakerConfig,
+        /// Consecutive validation failures per token: token -> (count, first_failure_time)
+        failure_counts: Mapping<AccountId, (u32, u64)>,
+        /// Role membership: (role, account) -> has_role
+        role_members: Mapping<(Role, AccountId), bool>,
+        /// Tokens with stored price data, in first-seen order (for enumeration)
+        tracked_tokens: Vec<AccountId>,
     }
 
     // ===== CONSTANTS =====
@@ -69,6 +124,30 @@ mod oracle {
     /// Special address representing DOT token for USD price feeds
     const DOT_TOKEN_ADDRESS: [u8; 32] = [0xFF; 32];
 
+    /// Number of price observations kept per token for TWAP
+    const MAX_PRICE_HISTORY: usize = 24;
+
+    /// Default cumulative deviation that trips the circuit breaker (50%)
+    const DEFAULT_CIRCUIT_BREAKER_BP: u32 = 5000;
+
+    /// Circuit breaker window, in multiples of min_update_interval
+    const CIRCUIT_BREAKER_WINDOW_INTERVALS: u64 = 60;
+
+    /// Default price precision (1 DOT = 10^10 plancks)
+    const PLANCK_DECIMALS: u8 = 10;
+
+    /// Maximum supported price precision (keeps scaling within u128)
+    const MAX_PRICE_DECIMALS: u8 = 18;
+
+    /// Feeds past this share of their heartbeat are reported as needing an update
+    const HEARTBEAT_WARNING_PERCENT: u64 = 90;
+
+    /// Maximum entries accepted by update_token_data_batch
+    const MAX_BATCH_SIZE: usize = 50;
+
+    /// Maximum entries returned per paged query
+    const MAX_PAGE_SIZE: u32 = 100;
+
     // ===== EXISTING EVENTS =====
 
     #[ink(event)]
@@ -89,6 +168,24 @@ mod oracle {
         volume: u128,
     }
 
+    #[ink(event)]
+    pub struct TokenDataRemoved {
+        #[ink(topic)]
+        token: AccountId,
+        removed_by: AccountId,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct BatchUpdateSummary {
+        total: u32,
+        succeeded: u32,
+        failed: u32,
+        #[ink(topic)]
+        updated_by: AccountId,
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct ValidationFailed {
         #[ink(topic)]
@@ -110,6 +207,74 @@ mod oracle {
         updater: AccountId,
     }
 
+    #[ink(event)]
+    pub struct TokenConfigUpdated {
+        #[ink(topic)]
+        token: AccountId,
+        /// None when the override was removed
+        config: Option<ValidationConfig>,
+    }
+
+    #[ink(event)]
+    pub struct PriceDecimalsUpdated {
+        #[ink(topic)]
+        token: AccountId,
+        decimals: u8,
+    }
+
+    #[ink(event)]
+    pub struct HeartbeatUpdated {
+        #[ink(topic)]
+        token: AccountId,
+        max_heartbeat_ms: u64,
+    }
+
+    #[ink(event)]
+    pub struct PriceSubmitted {
+        #[ink(topic)]
+        token: AccountId,
+        #[ink(topic)]
+        updater: AccountId,
+        price: u128,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct MinSourcesUpdated {
+        old_min_sources: u32,
+        new_min_sources: u32,
+    }
+
+    #[ink(event)]
+    pub struct CircuitBreakerTripped {
+        #[ink(topic)]
+        token: AccountId,
+        anchor_price: u128,
+        attempted_price: u128,
+        deviation_bp: u128,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct FailureBreakerTripped {
+        #[ink(topic)]
+        token: AccountId,
+        failure_count: u32,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct BreakerConfigUpdated {
+        failure_threshold: u32,
+        window_ms: u64,
+    }
+
+    #[ink(event)]
+    pub struct CircuitBreakerUpdated {
+        old_circuit_breaker_bp: u32,
+        new_circuit_breaker_bp: u32,
+    }
+
     #[ink(event)]
     pub struct EmergencyPause {
         paused: bool,
@@ -143,6 +308,24 @@ mod oracle {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        granted_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        revoked_by: AccountId,
+    }
+
     impl Default for Oracle {
         fn default() -> Self {
             Self::new()
@@ -156,10 +339,24 @@ mod oracle {
             let caller = Self::env().caller();
             Self {
                 token_data: Mapping::default(),
+                price_history: Mapping::default(),
+                submissions: Mapping::default(),
                 authorized_updaters: Mapping::default(),
+                updater_list: Vec::new(),
+                min_sources: 0,
                 validation_config: ValidationConfig::default(),
+                token_config_overrides: Mapping::default(),
+                heartbeats: Mapping::default(),
+                price_decimals: Mapping::default(),
                 owner: caller,
                 paused: false,
+                price_anchors: Mapping::default(),
+                anchor_epoch: 0,
+                circuit_breaker_bp: DEFAULT_CIRCUIT_BREAKER_BP,
+                breaker_config: BreakerConfig::default(),
+                failure_counts: Mapping::default(),
+                role_members: Mapping::default(),
+                tracked_tokens: Vec::new(),
             }
         }
 
@@ -176,8 +373,10 @@ mod oracle {
                 market_cap: 1_000_000_000_000_000, // 100,000 DOT
                 volume_24h: 100_000_000_000_000,   // 10,000 DOT
                 timestamp: oracle.env().block_timestamp(),
+                confidence_bp: 0,
+                last_updater: oracle.owner,
             };
-            oracle.token_data.insert(dummy_token, &sample_data);
+            oracle.store_token_data(dummy_token, &sample_data);
 
             // Set initial DOT/USD price: $6.50 USD (6.5 with 9 decimal places)
             let dot_address = AccountId::from(DOT_TOKEN_ADDRESS);
@@ -186,8 +385,10 @@ mod oracle {
                 market_cap: 0,        // Not applicable for DOT price feeds
                 volume_24h: 0,        // Not applicable for DOT price feeds
                 timestamp: oracle.env().block_timestamp(),
+                confidence_bp: 0,
+                last_updater: oracle.owner,
             };
-            oracle.token_data.insert(dot_address, &dot_usd_data);
+            oracle.store_token_data(dot_address, &dot_usd_data);
 
             oracle
         }
@@ -201,7 +402,7 @@ mod oracle {
             self.ensure_authorized()?;
 
             if usd_price == 0 {
-                return Err(Error::InvalidParameter);
+                return Err(Error::AmountZero);
             }
 
             let dot_address = AccountId::from(DOT_TOKEN_ADDRESS);
@@ -210,7 +411,7 @@ mod oracle {
             // Validate against existing DOT price if present
             if let Some(existing) = self.token_data.get(dot_address) {
                 self.validate_dot_price_update(usd_price, &existing)?;
-                self.validate_update_timing(&existing, timestamp)?;
+                self.validate_update_timing(dot_address, &existing, timestamp)?;
             }
 
             let dot_price_data = TokenPriceData {
@@ -218,9 +419,11 @@ mod oracle {
                 market_cap: 0,    // Not applicable for DOT
                 volume_24h: 0,    // Not applicable for DOT
                 timestamp,
+                confidence_bp: 0,
+                last_updater: self.env().caller(),
             };
 
-            self.token_data.insert(dot_address, &dot_price_data);
+            self.store_token_data(dot_address, &dot_price_data);
 
             self.env().emit_event(DotUsdPriceUpdated {
                 usd_price,
@@ -270,7 +473,7 @@ mod oracle {
             self.ensure_owner()?;
 
             if usd_price == 0 {
-                return Err(Error::InvalidParameter);
+                return Err(Error::AmountZero);
             }
 
             let dot_address = AccountId::from(DOT_TOKEN_ADDRESS);
@@ -281,9 +484,11 @@ mod oracle {
                 market_cap: 0,
                 volume_24h: 0,
                 timestamp,
+                confidence_bp: 0,
+                last_updater: self.env().caller(),
             };
 
-            self.token_data.insert(dot_address, &dot_price_data);
+            self.store_token_data(dot_address, &dot_price_data);
 
             self.env().emit_event(DotUsdPriceUpdated {
                 usd_price,
@@ -309,6 +514,10 @@ mod oracle {
         // ===== CORE DATA MANAGEMENT (existing methods, unchanged) =====
 
         /// Update complete token data with validation
+        /// Returns Ok(true) if the price was stored and Ok(false) if it was discarded.
+        /// A price rejected by validation returns Ok(false) rather than an error so it
+        /// still counts toward the failure breaker; see the `ValidationFailed` event.
+        /// If the circuit breaker trips, the update is discarded and the oracle paused.
         #[ink(message)]
         pub fn update_token_data(
             &mut self,
@@ -316,52 +525,321 @@ mod oracle {
             price: u128,
             market_cap: u128,
             volume: u128,
-        ) -> Result<(), Error> {
+        ) -> Result<bool, Error> {
             self.ensure_not_paused()?;
             self.ensure_authorized()?;
 
-            if price == 0 {
+            // Ok(false) (rejected or breaker tripped) must not revert, or the failure
+            // count and pause would be lost
+            self.apply_token_data_update(token, price, market_cap, volume, 0)
+        }
+
+        /// Update complete token data with the reporter's price uncertainty
+        /// Returns whether the price was stored, as in `update_token_data`.
+        #[ink(message)]
+        pub fn update_token_data_with_confidence(
+            &mut self,
+            token: AccountId,
+            price: u128,
+            market_cap: u128,
+            volume: u128,
+            confidence_bp: u32,
+        ) -> Result<bool, Error> {
+            self.ensure_not_paused()?;
+            self.ensure_authorized()?;
+
+            if confidence_bp > 10000 {
                 return Err(Error::InvalidParameter);
             }
 
-            let timestamp = self.env().block_timestamp();
+            self.apply_token_data_update(token, price, market_cap, volume, confidence_bp)
+        }
 
-            // Validate against existing data if present
-            if let Some(existing) = self.token_data.get(token) {
-                self.validate_price_update(token, price, &existing)?;
-                self.validate_update_timing(&existing, timestamp)?;
+        /// Update data for several tokens in one call
+        /// Entries failing validation are skipped; returns the number applied.
+        #[ink(message)]
+        pub fn update_token_data_batch(
+            &mut self,
+            updates: Vec<(AccountId, u128, u128, u128)>, // (token, price, market_cap, volume)
+        ) -> Result<u32, Error> {
+            self.ensure_not_paused()?;
+            self.ensure_authorized()?;
+
+            if updates.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidParameter);
             }
 
-            let new_data = TokenPriceData {
+            let total = updates.len() as u32;
+            let mut succeeded = 0u32;
+
+            for (token, price, market_cap, volume) in updates {
+                if self
+                    .apply_token_data_update(token, price, market_cap, volume, 0)
+                    .unwrap_or(false)
+                {
+                    succeeded = succeeded.saturating_add(1);
+                }
+            }
+
+            self.env().emit_event(BatchUpdateSummary {
+                total,
+                succeeded,
+                failed: total.saturating_sub(succeeded),
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(succeeded)
+        }
+
+        /// Get time-weighted average price over the last `window_ms` milliseconds
+        /// Each observation is weighted by how long it stayed current. Falls back to
+        /// the spot price when fewer than two observations fall inside the window.
+        #[ink(message)]
+        pub fn get_twap(&self, token: AccountId, window_ms: u64) -> Option<u128> {
+            let spot_price = self.get_price(token)?;
+
+            let current_time = self.env().block_timestamp();
+            let window_start = current_time.saturating_sub(window_ms);
+
+            let history = self.price_history.get(token).unwrap_or_default();
+            let in_window: Vec<(u64, u128)> = history
+                .into_iter()
+                .filter(|(timestamp, _)| *timestamp >= window_start)
+                .collect();
+
+            if in_window.len() < 2 {
+                return Some(spot_price);
+            }
+
+            let mut weighted_sum: u128 = 0;
+            let mut total_time: u128 = 0;
+
+            for (index, (timestamp, price)) in in_window.iter().enumerate() {
+                let period_end = in_window
+                    .get(index.saturating_add(1))
+                    .map(|(next_timestamp, _)| *next_timestamp)
+                    .unwrap_or(current_time);
+                let duration = period_end.saturating_sub(*timestamp) as u128;
+
+                weighted_sum = weighted_sum.checked_add(price.checked_mul(duration)?)?;
+                total_time = total_time.saturating_add(duration);
+            }
+
+            if total_time == 0 {
+                return Some(spot_price);
+            }
+
+            weighted_sum.checked_div(total_time)
+        }
+
+        /// Get recorded price observations for a token, oldest first
+        #[ink(message)]
+        pub fn get_price_history(&self, token: AccountId) -> Vec<(u64, u128)> {
+            self.price_history.get(token).unwrap_or_default()
+        }
+
+        /// Get complete token data
+        #[ink(message)]
+        pub fn get_token_data(&self, token: AccountId) -> Option<TokenPriceData> {
+            self.token_data.get(token)
+        }
+
+        /// Get tokens with stored price data, in first-seen order
+        /// At most `MAX_PAGE_SIZE` entries are returned per call.
+        #[ink(message)]
+        pub fn get_tracked_tokens(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+            self.tracked_tokens_page(offset, limit).to_vec()
+        }
+
+        /// Get price data for a page of tracked tokens (for indexers)
+        #[ink(message)]
+        pub fn get_all_token_data(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, TokenPriceData)> {
+            self.tracked_tokens_page(offset, limit)
+                .iter()
+                .filter_map(|token| self.token_data.get(*token).map(|data| (*token, data)))
+                .collect()
+        }
+
+        /// Get the number of tracked tokens
+        #[ink(message)]
+        pub fn get_tracked_token_count(&self) -> u32 {
+            self.tracked_tokens.len() as u32
+        }
+
+        /// Remove a token's price data and observations (owner only)
+        /// Per-token configuration (heartbeat, decimals, overrides) is kept.
+        #[ink(message)]
+        pub fn remove_token_data(&mut self, token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if self.token_data.take(token).is_none() {
+                return Err(Error::TokenNotFound);
+            }
+
+            self.price_history.remove(token);
+            self.price_anchors.remove(token);
+            self.failure_counts.remove(token);
+            for updater in &self.updater_list {
+                self.submissions.remove((token, *updater));
+            }
+            self.submissions.remove((token, self.owner));
+            self.tracked_tokens.retain(|tracked| *tracked != token);
+
+            self.env().emit_event(TokenDataRemoved {
+                token,
+                removed_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(())
+        }
+
+        /// Get only price (backward compatibility)
+        /// Returns the median across sources once at least `min_sources` have submitted.
+        #[ink(message)]
+        pub fn get_price(&self, token: AccountId) -> Option<u128> {
+            let price = self.token_data.get(token).map(|data| data.price)?;
+
+            if self.min_sources > 0 {
+                let source_prices = self.collect_submitted_prices(token, false);
+                if source_prices.len() >= self.min_sources as usize {
+                    return Self::median(source_prices).or(Some(price));
+                }
+            }
+
+            Some(price)
+        }
+
+        /// Get the median of the latest prices submitted by all authorized sources
+        #[ink(message)]
+        pub fn get_median_price(&self, token: AccountId) -> Option<u128> {
+            Self::median(self.collect_submitted_prices(token, false))
+        }
+
+        /// Get number of authorized sources with a price for the token
+        #[ink(message)]
+        pub fn get_source_count(&self, token: AccountId) -> u32 {
+            self.collect_submitted_prices(token, false).len() as u32
+        }
+
+        /// Submit a price as one of several reporters, without changing the official price
+        #[ink(message)]
+        pub fn submit_price(&mut self, token: AccountId, price: u128) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            self.ensure_authorized()?;
+
+            if price == 0 {
+                return Err(Error::AmountZero);
+            }
+
+            let caller = self.env().caller();
+            let timestamp = self.env().block_timestamp();
+            self.submissions
+                .insert((token, caller), &(price, timestamp));
+
+            self.env().emit_event(PriceSubmitted {
+                token,
+                updater: caller,
                 price,
-                market_cap,
-                volume_24h: volume,
                 timestamp,
+            });
+
+            Ok(())
+        }
+
+        /// Set the official price to the median of fresh submissions
+        /// Requires at least `min_sources` (minimum one) non-stale submissions. Returns the
+        /// official price, which is left unchanged if the circuit breaker trips.
+        #[ink(message)]
+        pub fn finalize_price(&mut self, token: AccountId) -> Result<u128, Error> {
+            self.ensure_not_paused()?;
+            self.ensure_authorized()?;
+
+            let fresh_prices = self.collect_submitted_prices(token, true);
+            if fresh_prices.len() < self.min_sources.max(1) as usize {
+                return Err(Error::OracleCallFailed);
+            }
+
+            let price = Self::median(fresh_prices).ok_or(Error::OracleCallFailed)?;
+            let timestamp = self.env().block_timestamp();
+
+            let existing = self.token_data.get(token);
+            let (market_cap, volume_24h) = match &existing {
+                Some(existing) => {
+                    self.validate_price_update(token, price, existing)?;
+                    (existing.market_cap, existing.volume_24h)
+                }
+                None => (0, 0),
             };
 
-            self.token_data.insert(token, &new_data);
+            if self.check_circuit_breaker(token, price, existing.as_ref(), timestamp) {
+                return Ok(existing.map(|data| data.price).unwrap_or(0));
+            }
+
+            self.store_token_data(
+                token,
+                &TokenPriceData {
+                    price,
+                    market_cap,
+                    volume_24h,
+                    timestamp,
+                    confidence_bp: 0,
+                    last_updater: self.env().caller(),
+                },
+            );
+            self.record_price_observation(token, timestamp, price);
 
             self.env().emit_event(PriceUpdated {
                 token,
                 price,
                 market_cap,
-                volume,
+                volume: volume_24h,
                 timestamp,
             });
 
+            Ok(price)
+        }
+
+        /// Get a reporter's latest submission as (price, timestamp)
+        #[ink(message)]
+        pub fn get_submission(&self, token: AccountId, updater: AccountId) -> Option<(u128, u64)> {
+            self.submissions.get((token, updater))
+        }
+
+        /// Set the decimal precision a token's price is reported in
+        #[ink(message)]
+        pub fn set_price_decimals(&mut self, token: AccountId, decimals: u8) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            self.ensure_authorized()?;
+
+            if decimals > MAX_PRICE_DECIMALS {
+                return Err(Error::InvalidParameter);
+            }
+
+            self.price_decimals.insert(token, &decimals);
+
+            self.env()
+                .emit_event(PriceDecimalsUpdated { token, decimals });
+
             Ok(())
         }
 
-        /// Get complete token data
+        /// Get the decimal precision of a token's price (plancks by default)
         #[ink(message)]
-        pub fn get_token_data(&self, token: AccountId) -> Option<TokenPriceData> {
-            self.token_data.get(token)
+        pub fn get_price_decimals(&self, token: AccountId) -> u8 {
+            self.price_decimals.get(token).unwrap_or(PLANCK_DECIMALS)
         }
 
-        /// Get only price (backward compatibility)
+        /// Get price with reporter uncertainty as (price, confidence_bp)
         #[ink(message)]
-        pub fn get_price(&self, token: AccountId) -> Option<u128> {
-            self.token_data.get(token).map(|data| data.price)
+        pub fn get_price_with_confidence(&self, token: AccountId) -> Option<(u128, u32)> {
+            let confidence_bp = self.token_data.get(token)?.confidence_bp;
+            self.get_price(token).map(|price| (price, confidence_bp))
         }
 
         /// Get market cap (backward compatibility)
@@ -380,21 +858,94 @@ mod oracle {
         #[ink(message)]
         pub fn is_price_stale(&self, token: AccountId) -> bool {
             match self.token_data.get(token) {
-                Some(data) => {
-                    let current_time = self.env().block_timestamp();
-                    // Fixed: Use checked multiplication to prevent overflow
-                    let staleness_threshold_ms = self
-                        .validation_config
-                        .staleness_threshold
-                        .checked_mul(1000)
-                        .unwrap_or(u64::MAX); // If overflow, consider everything stale
-
-                    current_time.saturating_sub(data.timestamp) > staleness_threshold_ms
-                }
+                Some(data) => self.is_timestamp_stale(token, data.timestamp),
                 None => true, // No data is considered stale
             }
         }
 
+        /// Fail with PriceStale unless the token was updated within its heartbeat
+        #[ink(message)]
+        pub fn assert_fresh(&self, token: AccountId) -> Result<(), Error> {
+            let data = self.token_data.get(token).ok_or(Error::TokenNotFound)?;
+            let age = self.env().block_timestamp().saturating_sub(data.timestamp);
+
+            if age > self.get_heartbeat(token) {
+                return Err(Error::PriceStale);
+            }
+
+            Ok(())
+        }
+
+        /// Get tokens that have no data or are close to missing their heartbeat
+        #[ink(message)]
+        pub fn get_feeds_needing_update(&self, tokens: Vec<AccountId>) -> Vec<AccountId> {
+            let current_time = self.env().block_timestamp();
+
+            tokens
+                .into_iter()
+                .take(MAX_BATCH_SIZE)
+                .filter(|token| match self.token_data.get(*token) {
+                    Some(data) => {
+                        let warning_age = self
+                            .get_heartbeat(*token)
+                            .saturating_mul(HEARTBEAT_WARNING_PERCENT)
+                            / 100;
+                        current_time.saturating_sub(data.timestamp) >= warning_age
+                    }
+                    None => true,
+                })
+                .collect()
+        }
+
+        /// Set required refresh interval for a token in milliseconds (owner only)
+        #[ink(message)]
+        pub fn set_heartbeat(
+            &mut self,
+            token: AccountId,
+            max_heartbeat_ms: u64,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if max_heartbeat_ms == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            self.heartbeats.insert(token, &max_heartbeat_ms);
+
+            self.env().emit_event(HeartbeatUpdated {
+                token,
+                max_heartbeat_ms,
+            });
+
+            Ok(())
+        }
+
+        /// Get required refresh interval for a token in milliseconds
+        #[ink(message)]
+        pub fn get_heartbeat(&self, token: AccountId) -> u64 {
+            self.heartbeats.get(token).unwrap_or_else(|| {
+                self.get_effective_config(token)
+                    .staleness_threshold
+                    .saturating_mul(1000)
+            })
+        }
+
+        /// Get the account that submitted the latest update
+        #[ink(message)]
+        pub fn get_last_updater(&self, token: AccountId) -> Option<AccountId> {
+            self.token_data.get(token).map(|data| data.last_updater)
+        }
+
+        /// Filter candidates down to tokens whose price is currently stale
+        #[ink(message)]
+        pub fn get_stale_tokens(&self, candidates: Vec<AccountId>) -> Vec<AccountId> {
+            candidates
+                .into_iter()
+                .take(MAX_BATCH_SIZE)
+                .filter(|token| self.is_price_stale(*token))
+                .collect()
+        }
+
         /// Get last update timestamp
         #[ink(message)]
         pub fn get_last_update_time(&self, token: AccountId) -> Option<u64> {
@@ -413,6 +964,9 @@ mod oracle {
             }
 
             self.authorized_updaters.insert(updater, &true);
+            if !self.updater_list.contains(&updater) {
+                self.updater_list.push(updater);
+            }
             self.env().emit_event(UpdaterAdded { updater });
             Ok(())
         }
@@ -427,6 +981,7 @@ mod oracle {
             }
 
             self.authorized_updaters.remove(updater);
+            self.updater_list.retain(|&account| account != updater);
             self.env().emit_event(UpdaterRemoved { updater });
             Ok(())
         }
@@ -437,22 +992,55 @@ mod oracle {
             account == self.owner || self.authorized_updaters.get(account).unwrap_or(false)
         }
 
+        /// Grant a role to an account (owner only)
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if account == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            self.role_members.insert((role, account), &true);
+
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                granted_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a role from an account (owner only)
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.role_members.remove((role, account));
+
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                revoked_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Check if an account has a specific role
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.role_members.get((role, account)).unwrap_or(false)
+        }
+
         // ===== CONFIGURATION MANAGEMENT (unchanged) =====
 
         /// Update complete validation configuration (owner only)
         #[ink(message)]
         pub fn set_validation_config(&mut self, config: ValidationConfig) -> Result<(), Error> {
             self.ensure_owner()?;
-
-            // Basic validation of config parameters
-            if config.max_deviation_bp > 10000 {
-                // > 100%
-                return Err(Error::InvalidParameter);
-            }
-
-            if config.staleness_threshold == 0 || config.min_update_interval == 0 {
-                return Err(Error::InvalidParameter);
-            }
+            Self::check_validation_config(&config)?;
 
             self.validation_config = config.clone();
 
@@ -529,6 +1117,146 @@ mod oracle {
             Ok(())
         }
 
+        /// Set number of sources required before get_price uses the median (owner only)
+        /// Zero disables median pricing.
+        #[ink(message)]
+        pub fn set_min_sources(&mut self, min_sources: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let old_min_sources = self.min_sources;
+            self.min_sources = min_sources;
+
+            self.env().emit_event(MinSourcesUpdated {
+                old_min_sources,
+                new_min_sources: min_sources,
+            });
+
+            Ok(())
+        }
+
+        /// Set cumulative deviation that auto-pauses the oracle (owner only)
+        #[ink(message)]
+        pub fn set_circuit_breaker_bp(&mut self, circuit_breaker_bp: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if circuit_breaker_bp == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            let old_circuit_breaker_bp = self.circuit_breaker_bp;
+            self.circuit_breaker_bp = circuit_breaker_bp;
+
+            self.env().emit_event(CircuitBreakerUpdated {
+                old_circuit_breaker_bp,
+                new_circuit_breaker_bp: circuit_breaker_bp,
+            });
+
+            Ok(())
+        }
+
+        /// Set failure-count breaker configuration (owner only)
+        #[ink(message)]
+        pub fn set_breaker_config(&mut self, config: BreakerConfig) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if config.failure_threshold == 0 || config.window_ms == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            self.breaker_config = config.clone();
+
+            self.env().emit_event(BreakerConfigUpdated {
+                failure_threshold: config.failure_threshold,
+                window_ms: config.window_ms,
+            });
+
+            Ok(())
+        }
+
+        /// Get failure-count breaker configuration
+        #[ink(message)]
+        pub fn get_breaker_config(&self) -> BreakerConfig {
+            self.breaker_config.clone()
+        }
+
+        /// Get consecutive validation failures recorded for a token within the window
+        #[ink(message)]
+        pub fn get_failure_count(&self, token: AccountId) -> u32 {
+            let current_time = self.env().block_timestamp();
+            match self.failure_counts.get(token) {
+                Some((count, first_failure))
+                    if current_time.saturating_sub(first_failure)
+                        <= self.breaker_config.window_ms =>
+                {
+                    count
+                }
+                _ => 0,
+            }
+        }
+
+        /// Clear a token's failure count (owner only); resume_updates lifts the pause
+        #[ink(message)]
+        pub fn reset_breaker(&mut self, token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.failure_counts.remove(token);
+            Ok(())
+        }
+
+        /// Get cumulative deviation that auto-pauses the oracle (basis points)
+        #[ink(message)]
+        pub fn get_circuit_breaker_bp(&self) -> u32 {
+            self.circuit_breaker_bp
+        }
+
+        /// Get number of sources required for median pricing
+        #[ink(message)]
+        pub fn get_min_sources(&self) -> u32 {
+            self.min_sources
+        }
+
+        /// Override validation rules for a single token (owner only)
+        #[ink(message)]
+        pub fn set_token_config(
+            &mut self,
+            token: AccountId,
+            config: ValidationConfig,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+            Self::check_validation_config(&config)?;
+
+            self.token_config_overrides.insert(token, &config);
+
+            self.env().emit_event(TokenConfigUpdated {
+                token,
+                config: Some(config),
+            });
+
+            Ok(())
+        }
+
+        /// Remove a token's override so the global configuration applies (owner only)
+        #[ink(message)]
+        pub fn remove_token_config(&mut self, token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.token_config_overrides.remove(token);
+
+            self.env().emit_event(TokenConfigUpdated {
+                token,
+                config: None,
+            });
+
+            Ok(())
+        }
+
+        /// Get the validation rules that apply to a token (override or global)
+        #[ink(message)]
+        pub fn get_effective_config(&self, token: AccountId) -> ValidationConfig {
+            self.token_config_overrides
+                .get(token)
+                .unwrap_or_else(|| self.validation_config.clone())
+        }
+
         /// Get current validation configuration
         #[ink(message)]
         pub fn get_validation_config(&self) -> ValidationConfig {
@@ -555,10 +1283,10 @@ mod oracle {
 
         // ===== EMERGENCY CONTROLS (unchanged) =====
 
-        /// Pause all price updates (owner only)
+        /// Pause all price updates (owner or EmergencyController)
         #[ink(message)]
         pub fn pause_updates(&mut self) -> Result<(), Error> {
-            self.ensure_owner()?;
+            self.ensure_role(Role::EmergencyController)?;
             self.paused = true;
 
             self.env().emit_event(EmergencyPause {
@@ -569,11 +1297,13 @@ mod oracle {
             Ok(())
         }
 
-        /// Resume price updates (owner only)
+        /// Resume price updates (owner or EmergencyController)
         #[ink(message)]
         pub fn resume_updates(&mut self) -> Result<(), Error> {
-            self.ensure_owner()?;
+            self.ensure_role(Role::EmergencyController)?;
             self.paused = false;
+            // Invalidate all drift anchors so they re-base on the next update
+            self.anchor_epoch = self.anchor_epoch.wrapping_add(1);
 
             self.env().emit_event(EmergencyPause {
                 paused: false,
@@ -595,7 +1325,7 @@ mod oracle {
             self.ensure_owner()?;
 
             if price == 0 {
-                return Err(Error::InvalidParameter);
+                return Err(Error::AmountZero);
             }
 
             let timestamp = self.env().block_timestamp();
@@ -604,9 +1334,11 @@ mod oracle {
                 market_cap,
                 volume_24h: volume,
                 timestamp,
+                confidence_bp: 0,
+                last_updater: self.env().caller(),
             };
 
-            self.token_data.insert(token, &new_data);
+            self.store_token_data(token, &new_data);
 
             self.env().emit_event(PriceUpdated {
                 token,
@@ -628,8 +1360,9 @@ mod oracle {
         // ===== BACKWARD COMPATIBILITY (unchanged) =====
 
         /// Legacy update price method
+        /// Returns whether the price was stored, as in `update_token_data`.
         #[ink(message)]
-        pub fn update_price(&mut self, token: AccountId, price: u128) -> Result<(), Error> {
+        pub fn update_price(&mut self, token: AccountId, price: u128) -> Result<bool, Error> {
             // Get existing data or use defaults
             let existing = self.token_data.get(token);
             let (market_cap, volume) = match existing {
@@ -657,7 +1390,8 @@ mod oracle {
                     data.market_cap = market_cap;
                     data.volume_24h = volume;
                     data.timestamp = self.env().block_timestamp();
-                    self.token_data.insert(token, &data);
+                    data.last_updater = self.env().caller();
+                    self.store_token_data(token, &data);
                 }
                 None => {
                     return Err(Error::InvalidParameter); // Cannot update market data without price
@@ -688,6 +1422,15 @@ mod oracle {
             Ok(())
         }
 
+        fn ensure_role(&self, role: Role) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller == self.owner || self.has_role(role, caller) {
+                Ok(())
+            } else {
+                Err(Error::UnauthorizedRole)
+            }
+        }
+
         fn ensure_authorized(&self) -> Result<(), Error> {
             let caller = self.env().caller();
             if !self.is_authorized_updater(caller) {
@@ -698,8 +1441,244 @@ mod oracle {
 
         fn ensure_not_paused(&self) -> Result<(), Error> {
             if self.paused {
-                return Err(Error::OracleCallFailed); // Reuse existing error
+                return Err(Error::ContractPaused);
+            }
+            Ok(())
+        }
+
+        /// Store price data, tracking the token the first time it is seen
+        fn store_token_data(&mut self, token: AccountId, data: &TokenPriceData) {
+            if !self.token_data.contains(token) {
+                self.tracked_tokens.push(token);
+            }
+            self.token_data.insert(token, data);
+        }
+
+        /// Slice of `tracked_tokens` for a page request
+        fn tracked_tokens_page(&self, offset: u32, limit: u32) -> &[AccountId] {
+            let len = self.tracked_tokens.len();
+            let start = (offset as usize).min(len);
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE) as usize)
+                .min(len);
+            &self.tracked_tokens[start..end]
+        }
+
+        fn apply_token_data_update(
+            &mut self,
+            token: AccountId,
+            price: u128,
+            market_cap: u128,
+            volume: u128,
+            confidence_bp: u32,
+        ) -> Result<bool, Error> {
+            self.ensure_not_paused()?;
+
+            if price == 0 {
+                return Err(Error::AmountZero);
+            }
+
+            let timestamp = self.env().block_timestamp();
+
+            // Validate against existing data if present
+            let existing = self.token_data.get(token);
+            if let Some(existing) = &existing {
+                if self.validate_price_update(token, price, existing).is_err() {
+                    // Returning Err would revert the failure count, so a rejected
+                    // price is reported via ValidationFailed and Ok(false) instead
+                    self.record_validation_failure(token, timestamp);
+                    return Ok(false);
+                }
+                self.validate_update_timing(token, existing, timestamp)?;
+            }
+
+            if self.check_circuit_breaker(token, price, existing.as_ref(), timestamp) {
+                return Ok(false);
+            }
+
+            let new_data = TokenPriceData {
+                price,
+                market_cap,
+                volume_24h: volume,
+                timestamp,
+                confidence_bp,
+                last_updater: self.env().caller(),
+            };
+
+            self.store_token_data(token, &new_data);
+            self.failure_counts.remove(token);
+            self.submissions
+                .insert((token, self.env().caller()), &(price, timestamp));
+            self.record_price_observation(token, timestamp, price);
+
+            self.env().emit_event(PriceUpdated {
+                token,
+                price,
+                market_cap,
+                volume,
+                timestamp,
+            });
+
+            Ok(true)
+        }
+
+        /// Count a rejected price and pause the oracle once the threshold is reached
+        fn record_validation_failure(&mut self, token: AccountId, timestamp: u64) {
+            let failure_count = match self.failure_counts.get(token) {
+                Some((count, first_failure))
+                    if timestamp.saturating_sub(first_failure) <= self.breaker_config.window_ms =>
+                {
+                    self.failure_counts
+                        .insert(token, &(count.saturating_add(1), first_failure));
+                    count.saturating_add(1)
+                }
+                _ => {
+                    self.failure_counts.insert(token, &(1, timestamp));
+                    1
+                }
+            };
+
+            if failure_count < self.breaker_config.failure_threshold {
+                return;
+            }
+
+            self.paused = true;
+
+            self.env().emit_event(FailureBreakerTripped {
+                token,
+                failure_count,
+                timestamp,
+            });
+            self.env().emit_event(EmergencyPause {
+                paused: true,
+                timestamp,
+            });
+        }
+
+        /// Pause the oracle if `price` drifted too far from the token's anchor
+        /// Returns true when the breaker tripped. The anchor is re-set from the
+        /// current price once the window has elapsed.
+        fn check_circuit_breaker(
+            &mut self,
+            token: AccountId,
+            price: u128,
+            existing: Option<&TokenPriceData>,
+            timestamp: u64,
+        ) -> bool {
+            let window_ms = self
+                .get_effective_config(token)
+                .min_update_interval
+                .saturating_mul(CIRCUIT_BREAKER_WINDOW_INTERVALS)
+                .saturating_mul(1000);
+
+            let anchor = self
+                .price_anchors
+                .get(token)
+                .filter(|(_, anchor_time, epoch)| {
+                    *epoch == self.anchor_epoch
+                        && timestamp.saturating_sub(*anchor_time) <= window_ms
+                });
+
+            let (anchor_price, _, _) = match anchor {
+                Some(anchor) => anchor,
+                None => {
+                    let anchor_price = existing.map(|data| data.price).unwrap_or(price);
+                    let anchor = (anchor_price, timestamp, self.anchor_epoch);
+                    self.price_anchors.insert(token, &anchor);
+                    anchor
+                }
+            };
+
+            if anchor_price == 0 {
+                return false;
+            }
+
+            let deviation_bp = price
+                .abs_diff(anchor_price)
+                .saturating_mul(10000)
+                .checked_div(anchor_price)
+                .unwrap_or(u128::MAX);
+
+            if deviation_bp <= self.circuit_breaker_bp as u128 {
+                return false;
+            }
+
+            self.paused = true;
+
+            self.env().emit_event(CircuitBreakerTripped {
+                token,
+                anchor_price,
+                attempted_price: price,
+                deviation_bp,
+                timestamp,
+            });
+            self.env().emit_event(EmergencyPause {
+                paused: true,
+                timestamp,
+            });
+
+            true
+        }
+
+        fn collect_submitted_prices(&self, token: AccountId, fresh_only: bool) -> Vec<u128> {
+            core::iter::once(self.owner)
+                .chain(self.updater_list.iter().copied())
+                .filter_map(|source| self.submissions.get((token, source)))
+                .filter(|(_, timestamp)| !fresh_only || !self.is_timestamp_stale(token, *timestamp))
+                .map(|(price, _)| price)
+                .collect()
+        }
+
+        fn is_timestamp_stale(&self, token: AccountId, timestamp: u64) -> bool {
+            let current_time = self.env().block_timestamp();
+            // Fixed: Use checked multiplication to prevent overflow
+            let staleness_threshold_ms = self
+                .get_effective_config(token)
+                .staleness_threshold
+                .checked_mul(1000)
+                .unwrap_or(u64::MAX); // If overflow, consider everything stale
+
+            current_time.saturating_sub(timestamp) > staleness_threshold_ms
+        }
+
+        fn median(mut prices: Vec<u128>) -> Option<u128> {
+            if prices.is_empty() {
+                return None;
+            }
+
+            prices.sort_unstable();
+            let mid = prices.len() / 2;
+
+            if prices.len() % 2 == 1 {
+                return prices.get(mid).copied();
+            }
+
+            let low = *prices.get(mid.checked_sub(1)?)?;
+            let high = *prices.get(mid)?;
+            Some(low.saturating_add(high.saturating_sub(low) / 2))
+        }
+
+        fn record_price_observation(&mut self, token: AccountId, timestamp: u64, price: u128) {
+            let mut history = self.price_history.get(token).unwrap_or_default();
+            history.push((timestamp, price));
+            if history.len() > MAX_PRICE_HISTORY {
+                let excess = history.len().saturating_sub(MAX_PRICE_HISTORY);
+                history.drain(..excess);
+            }
+            self.price_history.insert(token, &history);
+        }
+
+        fn check_validation_config(config: &ValidationConfig) -> Result<(), Error> {
+            // Basic validation of config parameters
+            if config.max_deviation_bp > 10000 {
+                // > 100%
+                return Err(Error::InvalidParameter);
+            }
+
+            if config.staleness_threshold == 0 || config.min_update_interval == 0 {
+                return Err(Error::InvalidParameter);
             }
+
             Ok(())
         }
 
@@ -722,9 +1701,9 @@ mod oracle {
                 match price_diff.checked_mul(10000) {
                     Some(result) => match result.checked_div(old_price) {
                         Some(change) => change,
-                        None => return Err(Error::InvalidParameter), // Division error
+                        None => return Err(Error::InvalidParameter), // Zero divisor
                     },
-                    None => return Err(Error::InvalidParameter), // Price change too large
+                    None => return Err(Error::ArithmeticOverflow), // Price change too large
                 }
             } else {
                 let price_diff = old_price.saturating_sub(new_price);
@@ -732,13 +1711,13 @@ mod oracle {
                 match price_diff.checked_mul(10000) {
                     Some(result) => match result.checked_div(old_price) {
                         Some(change) => change,
-                        None => return Err(Error::InvalidParameter), // Division error
+                        None => return Err(Error::InvalidParameter), // Zero divisor
                     },
-                    None => return Err(Error::InvalidParameter), // Price change too large
+                    None => return Err(Error::ArithmeticOverflow), // Price change too large
                 }
             };
 
-            if change_bp > self.validation_config.max_deviation_bp as u128 {
+            if change_bp > self.get_effective_config(token).max_deviation_bp as u128 {
                 self.env().emit_event(ValidationFailed {
                     token,
                     reason: "Price deviation too high".into(),
@@ -771,7 +1750,7 @@ mod oracle {
                         Some(change) => change,
                         None => return Err(Error::InvalidParameter),
                     },
-                    None => return Err(Error::InvalidParameter),
+                    None => return Err(Error::ArithmeticOverflow),
                 }
             } else {
                 let price_diff = old_price.saturating_sub(new_price);
@@ -780,7 +1759,7 @@ mod oracle {
                         Some(change) => change,
                         None => return Err(Error::InvalidParameter),
                     },
-                    None => return Err(Error::InvalidParameter),
+                    None => return Err(Error::ArithmeticOverflow),
                 }
             };
 
@@ -799,16 +1778,20 @@ mod oracle {
 
         fn validate_update_timing(
             &self,
+            token: AccountId,
             existing: &TokenPriceData,
             new_timestamp: u64,
         ) -> Result<(), Error> {
             let time_diff = new_timestamp.saturating_sub(existing.timestamp);
 
             // Fixed: Use checked multiplication to prevent overflow
-            let min_interval_ms = match self.validation_config.min_update_interval.checked_mul(1000)
+            let min_interval_ms = match self
+                .get_effective_config(token)
+                .min_update_interval
+                .checked_mul(1000)
             {
                 Some(result) => result,
-                None => return Err(Error::InvalidParameter), // Invalid configuration
+                None => return Err(Error::ArithmeticOverflow), // Invalid configuration
             };
 
             if time_diff < min_interval_ms {
@@ -818,4 +1801,302 @@ mod oracle {
             Ok(())
         }
     }
+
+    impl shared::Oracle for Oracle {
+        /// Get the current price of a token in plancks
+        #[ink(message)]
+        fn get_price(&self, token: AccountId) -> Option<u128> {
+            Oracle::get_price(self, token)
+        }
+
+        /// Get the market cap of a token in plancks
+        #[ink(message)]
+        fn get_market_cap(&self, token: AccountId) -> Option<u128> {
+            Oracle::get_market_cap(self, token)
+        }
+
+        /// Get the market volume of a token in plancks
+        #[ink(message)]
+        fn get_market_volume(&self, token: AccountId) -> Option<u128> {
+            Oracle::get_market_volume(self, token)
+        }
+
+        /// Check whether a token's price is older than its staleness threshold
+        #[ink(message)]
+        fn is_price_stale(&self, token: AccountId) -> bool {
+            Oracle::is_price_stale(self, token)
+        }
+
+        /// Get the DOT/USD price
+        #[ink(message)]
+        fn get_dot_usd_price(&self) -> Option<u128> {
+            Oracle::get_dot_usd_price(self)
+        }
+
+        /// Check whether the DOT/USD price is stale
+        #[ink(message)]
+        fn is_dot_price_stale(&self) -> bool {
+            Oracle::is_dot_price_stale(self)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn assert_implements_oracle<T: shared::Oracle>() {}
+
+        fn set_time(timestamp: u64) {
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+        }
+
+        #[test]
+        fn oracle_implements_shared_trait() {
+            assert_implements_oracle::<Oracle>();
+        }
+
+        #[ink::test]
+        fn emergency_controller_can_pause_but_not_configure() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut oracle = Oracle::new();
+            oracle
+                .grant_role(Role::EmergencyController, accounts.bob)
+                .unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(oracle.pause_updates(), Ok(()));
+            assert!(oracle.is_paused());
+            assert_eq!(oracle.set_max_deviation(500), Err(Error::Unauthorized));
+            assert_eq!(oracle.resume_updates(), Ok(()));
+            assert!(!oracle.is_paused());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(oracle.pause_updates(), Err(Error::UnauthorizedRole));
+        }
+
+        #[ink::test]
+        fn repeated_rejected_single_updates_trip_failure_breaker() {
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            assert_eq!(
+                oracle.update_token_data(token, 1_000, 10_000, 100),
+                Ok(true)
+            );
+
+            let threshold = oracle.get_breaker_config().failure_threshold;
+            for attempt in 1..=threshold {
+                assert!(!oracle.is_paused());
+                // 10x the stored price is far beyond the allowed deviation
+                assert_eq!(
+                    oracle.update_token_data(token, 10_000, 10_000, 100),
+                    Ok(false)
+                );
+                assert_eq!(oracle.get_failure_count(token), attempt);
+            }
+
+            assert!(oracle.is_paused());
+            assert_eq!(oracle.get_token_data(token).unwrap().price, 1_000);
+        }
+
+        #[ink::test]
+        fn twap_weights_by_duration_and_skips_old_observations() {
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            oracle.update_token_data(token, 1_000, 10_000, 100).unwrap();
+            set_time(60_000);
+            oracle.update_token_data(token, 1_100, 10_000, 100).unwrap();
+            set_time(180_000);
+
+            // 1_000 held for 60s, 1_100 for 120s
+            assert_eq!(oracle.get_twap(token, 1_000_000), Some(1_066));
+            // Only the latest observation is inside the window: spot price
+            assert_eq!(oracle.get_twap(token, 150_000), Some(1_100));
+            assert_eq!(oracle.get_twap(AccountId::from([0x02; 32]), 1_000), None);
+        }
+
+        #[ink::test]
+        fn price_history_keeps_latest_samples() {
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            for step in 0..30u64 {
+                set_time(step * 60_000);
+                oracle.update_token_data(token, 1_000, 10_000, 100).unwrap();
+            }
+
+            let history = oracle.get_price_history(token);
+            assert_eq!(history.len(), MAX_PRICE_HISTORY);
+            assert_eq!(history.first(), Some(&(6 * 60_000, 1_000)));
+            assert_eq!(history.last(), Some(&(29 * 60_000, 1_000)));
+        }
+
+        #[ink::test]
+        fn median_handles_odd_and_even_source_counts() {
+            assert_eq!(Oracle::median(Vec::new()), None);
+            assert_eq!(Oracle::median(vec![30, 10, 20]), Some(20));
+            assert_eq!(Oracle::median(vec![40, 10, 30, 20]), Some(25));
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            oracle.add_updater(accounts.bob).unwrap();
+            oracle.submit_price(token, 1_000).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            oracle.submit_price(token, 1_300).unwrap();
+
+            assert_eq!(oracle.get_source_count(token), 2);
+            assert_eq!(oracle.get_median_price(token), Some(1_150));
+        }
+
+        #[ink::test]
+        fn finalize_price_needs_min_fresh_sources() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            oracle.add_updater(accounts.bob).unwrap();
+            oracle.add_updater(accounts.charlie).unwrap();
+            oracle.submit_price(token, 1_000).unwrap();
+
+            // The owner's submission goes stale before the others arrive
+            set_time(3_700_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            oracle.submit_price(token, 1_100).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            oracle.submit_price(token, 1_200).unwrap();
+            assert_eq!(oracle.get_median_price(token), Some(1_100));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            oracle.set_min_sources(3).unwrap();
+            assert_eq!(oracle.finalize_price(token), Err(Error::OracleCallFailed));
+            assert_eq!(oracle.get_token_data(token), None);
+
+            oracle.set_min_sources(2).unwrap();
+            assert_eq!(oracle.finalize_price(token), Ok(1_150));
+            assert_eq!(oracle.get_token_data(token).unwrap().price, 1_150);
+        }
+
+        #[ink::test]
+        fn drift_from_anchor_trips_circuit_breaker() {
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            oracle.update_token_data(token, 1_000, 10_000, 100).unwrap();
+
+            // Each step stays within the 20% deviation limit
+            set_time(60_000);
+            assert_eq!(
+                oracle.update_token_data(token, 1_190, 10_000, 100),
+                Ok(true)
+            );
+            set_time(120_000);
+            assert_eq!(
+                oracle.update_token_data(token, 1_410, 10_000, 100),
+                Ok(true)
+            );
+            assert!(!oracle.is_paused());
+
+            // ...but 1_670 is 67% above the 1_000 anchor
+            set_time(180_000);
+            assert_eq!(
+                oracle.update_token_data(token, 1_670, 10_000, 100),
+                Ok(false)
+            );
+            assert!(oracle.is_paused());
+            assert_eq!(oracle.get_token_data(token).unwrap().price, 1_410);
+            assert_eq!(oracle.get_failure_count(token), 0);
+        }
+
+        #[ink::test]
+        fn token_override_takes_precedence_over_global_config() {
+            let mut oracle = Oracle::new();
+            let strict = AccountId::from([0x01; 32]);
+            let other = AccountId::from([0x02; 32]);
+            let config = ValidationConfig {
+                max_deviation_bp: 500,
+                ..ValidationConfig::default()
+            };
+            oracle.set_token_config(strict, config.clone()).unwrap();
+            assert_eq!(oracle.get_effective_config(strict), config);
+            assert_eq!(
+                oracle.get_effective_config(other),
+                ValidationConfig::default()
+            );
+
+            for token in [strict, other] {
+                oracle.update_token_data(token, 1_000, 10_000, 100).unwrap();
+            }
+            set_time(60_000);
+            // A 10% move breaks the 5% override but not the 20% global limit
+            assert_eq!(
+                oracle.update_token_data(strict, 1_100, 10_000, 100),
+                Ok(false)
+            );
+            assert_eq!(
+                oracle.update_token_data(other, 1_100, 10_000, 100),
+                Ok(true)
+            );
+
+            oracle.remove_token_config(strict).unwrap();
+            assert_eq!(
+                oracle.get_effective_config(strict),
+                ValidationConfig::default()
+            );
+        }
+
+        #[ink::test]
+        fn heartbeat_warning_flags_feeds_near_deadline() {
+            let mut oracle = Oracle::new();
+            let token = AccountId::from([0x01; 32]);
+            let unknown = AccountId::from([0x02; 32]);
+            assert_eq!(oracle.get_heartbeat(token), 3_600_000);
+            oracle.set_heartbeat(token, 100_000).unwrap();
+            oracle.update_token_data(token, 1_000, 10_000, 100).unwrap();
+
+            // Warning starts at 90% of the heartbeat
+            set_time(89_999);
+            assert_eq!(
+                oracle.get_feeds_needing_update(vec![token, unknown]),
+                vec![unknown]
+            );
+            set_time(90_000);
+            assert_eq!(
+                oracle.get_feeds_needing_update(vec![token, unknown]),
+                vec![token, unknown]
+            );
+
+            set_time(100_000);
+            assert_eq!(oracle.assert_fresh(token), Ok(()));
+            set_time(100_001);
+            assert_eq!(oracle.assert_fresh(token), Err(Error::PriceStale));
+        }
+
+        #[ink::test]
+        fn tracked_tokens_page_through_price_data() {
+            let mut oracle = Oracle::new();
+            let tokens: Vec<AccountId> =
+                (1..=3u8).map(|byte| AccountId::from([byte; 32])).collect();
+            for token in &tokens {
+                oracle
+                    .update_token_data(*token, 1_000, 10_000, 100)
+                    .unwrap();
+            }
+            // A second update does not track the token twice
+            oracle.update_market_data(tokens[0], 20_000, 200).unwrap();
+
+            assert_eq!(oracle.get_tracked_token_count(), 3);
+            assert_eq!(oracle.get_tracked_tokens(0, 2), tokens[..2].to_vec());
+            assert_eq!(oracle.get_tracked_tokens(2, 2), tokens[2..].to_vec());
+            assert!(oracle.get_tracked_tokens(3, 2).is_empty());
+
+            let page = oracle.get_all_token_data(0, 1);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].0, tokens[0]);
+            assert_eq!(page[0].1.market_cap, 20_000);
+
+            assert_eq!(oracle.remove_token_data(tokens[1]), Ok(()));
+            assert_eq!(oracle.get_tracked_tokens(0, 10), vec![tokens[0], tokens[2]]);
+            assert_eq!(
+                oracle.remove_token_data(tokens[1]),
+                Err(Error::TokenNotFound)
+            );
+        }
+    }
 }
@@ -4,11 +4,17 @@
 
 #[ink::contract]
 mod portfolio {
+    use ink::codegen::TraitCallBuilder;
+    use ink::prelude::format;
     use ink::prelude::string::String;
+    use ink::prelude::vec; // Import the vec! macro
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
-    use shared::Error; // Assuming we'll use shared error types
-    use ink::prelude::format;
+    use shared::non_reentrant;
+    use shared::psp22::PSP22Error;
+    use shared::utils::reentrancy_guard::ReentrancyGuard;
+    use shared::{Error, Role}; // Assuming we'll use shared error types
+
     // ===== CORE DATA TYPES =====
 
     /// Portfolio state for emergency controls
@@ -24,6 +30,20 @@ mod portfolio {
         Emergency,   // Emergency state - withdrawals only
     }
 
+    /// Bitflags for partial pausing of portfolio operations
+    pub struct PauseFlags;
+
+    impl PauseFlags {
+        /// Holdings changes
+        pub const TRADES: u8 = 1 << 0;
+        /// USDC and in-kind deposits
+        pub const DEPOSITS: u8 = 1 << 1;
+        /// DEX rebalancing
+        pub const REBALANCING: u8 = 1 << 2;
+        /// All pausable operations
+        pub const ALL: u8 = Self::TRADES | Self::DEPOSITS | Self::REBALANCING;
+    }
+
     impl Default for PortfolioState {
         fn default() -> Self {
             Self::Active
@@ -81,6 +101,27 @@ mod portfolio {
         pub holdings: Vec<(u32, TokenHolding)>, // (token_id, holding_data)
     }
 
+    /// Single-call health report for operators
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PortfolioHealth {
+        pub total_value: u128,
+        pub index_value: u128,
+        pub performance_bp: i32,
+        pub max_drift_bp: u32, // Largest absolute deviation of a holding from its target
+        pub needs_rebalance: bool, // max_drift_bp exceeds rebalance_threshold_bp
+        pub liquidity_ok: bool, // usdc_balance covers min_liquidity_buffer
+        pub stale_prices: u32, // Held tokens with stale or unavailable prices
+    }
+
+    /// Direction of a rebalancing swap relative to the held token
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RebalanceDirection {
+        Buy,  // USDC -> token (holding is underweight)
+        Sell, // token -> USDC (holding is overweight)
+    }
+
     /// Enhanced token data from Registry (local copy for type compatibility)
     #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -95,6 +136,21 @@ mod portfolio {
         pub price: u128,
     }
 
+    impl From<shared::EnrichedTokenData> for EnrichedTokenData {
+        fn from(data: shared::EnrichedTokenData) -> Self {
+            Self {
+                token_contract: data.token_contract,
+                oracle_contract: data.oracle_contract,
+                balance: data.balance,
+                weight_investment: data.weight_investment,
+                tier: data.tier,
+                market_cap: data.market_cap,
+                market_volume: data.market_volume,
+                price: data.price,
+            }
+        }
+    }
+
     // ===== MAIN CONTRACT STORAGE =====
 
     #[ink(storage)]
@@ -114,9 +170,11 @@ mod portfolio {
         held_token_ids: Vec<u32>,
         /// Total number of unique tokens held
         total_tokens_held: u32,
+        /// Held tokens no longer in the Registry's active tier, as of the last sync
+        flagged_for_removal: Vec<u32>,
 
         // ===== INDEX BASE VALUE SYSTEM =====
-        /// Fixed base value: $100 in plancks (immutable)
+        /// Fixed base value set at deployment, default $100 in plancks (immutable)
         index_base_value: u128,
         /// Total portfolio value at deployment (immutable baseline)
         base_portfolio_value: u128,
@@ -126,6 +184,22 @@ mod portfolio {
         last_index_update: u64,
         /// Index calculation enabled flag
         index_tracking_enabled: bool,
+        /// Total portfolio value from the last index update
+        cached_total_value: u128,
+        /// When the cached portfolio value was computed (0 = never)
+        cache_timestamp: u64,
+        /// Age in milliseconds after which cached values are considered stale
+        cache_staleness_threshold: u64,
+        /// Age in milliseconds after which the index value is considered stale
+        index_staleness_threshold_ms: u64,
+        /// Fail valuation on stale or missing prices instead of falling back to amounts
+        require_fresh_prices: bool,
+        /// Last known fresh DOT/USD price (9 decimals), used when the oracle is unavailable
+        last_dot_usd_price: u128,
+        /// When the last known DOT/USD price was cached
+        last_dot_usd_update: u64,
+        /// Automation account allowed to refresh a stale index value
+        keeper: Option<AccountId>,
 
         // ===== FEE SYSTEM =====
         /// Fee configuration
@@ -134,8 +208,16 @@ mod portfolio {
         collected_fees: Mapping<u32, u128>,
         /// Last streaming fee collection timestamp per user: user -> timestamp
         last_streaming_fee: Mapping<AccountId, u64>,
+        /// Role-based access control: (Role, AccountId) -> bool
+        role_members: Mapping<(Role, AccountId), bool>,
+        /// Reentrancy guard for messages that make cross-contract calls
+        reentrancy_guard: ReentrancyGuard,
         /// Fee beneficiary addresses and their share: beneficiary -> share_bp
         fee_beneficiaries: Mapping<AccountId, u32>,
+        /// List of fee beneficiaries (for iteration)
+        fee_beneficiary_list: Vec<AccountId>,
+        /// Sum of all beneficiary shares in basis points (never above 10000)
+        total_beneficiary_share_bp: u32,
         /// Total collected fees in USDC equivalent
         total_fees_collected: u128,
 
@@ -148,6 +230,8 @@ mod portfolio {
         dex_contract: Option<AccountId>,
         /// Oracle contract for price feeds (usually accessed via Registry)
         oracle_contract: Option<AccountId>,
+        /// USDC token contract (quote asset for rebalancing swaps)
+        usdc_contract: Option<AccountId>,
 
         // ===== PORTFOLIO MANAGEMENT =====
         /// Maximum number of tokens portfolio can hold
@@ -158,8 +242,14 @@ mod portfolio {
         last_rebalance: u64,
         /// Rebalancing threshold in basis points (e.g., 500 = 5% deviation triggers rebalance)
         rebalance_threshold_bp: u32,
+        /// Minimum time between rebalances in milliseconds
+        min_rebalance_interval_ms: u64,
+        /// Emit `RebalanceNeeded` after holding changes when drift exceeds the threshold
+        auto_rebalance_enabled: bool,
         /// Emergency pause flag for all operations
         emergency_paused: bool,
+        /// Partial pause flags (see `PauseFlags`), independent of the emergency pause
+        pause_flags: u8,
 
         // ===== LIQUIDITY & RISK MANAGEMENT =====
         /// Minimum USDC buffer for liquidity
@@ -215,6 +305,13 @@ mod portfolio {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct HoldingFlaggedForRemoval {
+        #[ink(topic)]
+        token_id: u32,
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct TokenHoldingRemoved {
         #[ink(topic)]
@@ -240,6 +337,131 @@ mod portfolio {
         base_portfolio_value: u128,
         deployment_timestamp: u64,
         set_by: AccountId,
+        staleness_overridden: bool, // Baseline set despite stale prices
+    }
+
+    // Rebalancing Events
+    #[ink(event)]
+    pub struct RebalanceExecuted {
+        #[ink(topic)]
+        token_id: u32,
+        direction: RebalanceDirection,
+        amount_in: u128,
+        amount_out: u128,
+        executed_by: AccountId,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct RebalanceNeeded {
+        threshold_bp: u32,
+        timestamp: u64,
+    }
+
+    // Mint & Redeem Events
+    #[ink(event)]
+    pub struct IndexTokensMinted {
+        #[ink(topic)]
+        user: AccountId,
+        usdc_amount: u128,
+        fee_amount: u128,
+        tokens_minted: u128,
+        index_value: u128,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct IndexTokensRedeemed {
+        #[ink(topic)]
+        user: AccountId,
+        tokens_burned: u128,
+        fee_amount: u128,
+        usdc_returned: u128,
+        index_value: u128,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyWithdrawal {
+        #[ink(topic)]
+        user: AccountId,
+        tokens_burned: u128,
+        usdc_returned: u128,
+        holdings_returned: Vec<(u32, u128)>, // (token_id, amount)
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyRedemption {
+        #[ink(topic)]
+        user: AccountId,
+        shares_burned: u128,
+        tokens_returned: Vec<(u32, u128)>, // (token_id, amount)
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct SharesMinted {
+        #[ink(topic)]
+        user: AccountId,
+        #[ink(topic)]
+        token_id: u32,
+        amount_deposited: u128,
+        fee_amount: u128,
+        deposit_value: u128,
+        shares_minted: u128,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct SharesBurned {
+        #[ink(topic)]
+        user: AccountId,
+        shares_burned: u128,
+        fee_value: u128,
+        value_returned: u128,
+        usdc_returned: u128,
+        tokens_returned: Vec<(u32, u128)>, // (token_id, amount)
+        timestamp: u64,
+    }
+
+    // Liquidity Events
+    #[ink(event)]
+    pub struct TokenSwept {
+        #[ink(topic)]
+        token_contract: AccountId,
+        to: AccountId,
+        amount: u128,
+        swept_by: AccountId,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct LiquidityBufferChanged {
+        old_balance: u128,
+        new_balance: u128,
+        #[ink(topic)]
+        changed_by: AccountId,
+        timestamp: u64,
+    }
+
+    // Access Control Events
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        granted_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        revoked_by: AccountId,
     }
 
     // Configuration Events
@@ -251,6 +473,25 @@ mod portfolio {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct FeeBeneficiaryUpdated {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        old_share_bp: u32,
+        new_share_bp: u32,
+        updated_by: AccountId,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct FeesDistributed {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        amount: u128,
+        share_bp: u32,
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct ContractReferenceUpdated {
         contract_type: String, // "registry", "token", "dex", "oracle"
@@ -260,6 +501,27 @@ mod portfolio {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct StalenessThresholdUpdated {
+        old_threshold_ms: u64,
+        new_threshold_ms: u64,
+        updated_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IndexStalenessThresholdUpdated {
+        old_threshold_ms: u64,
+        new_threshold_ms: u64,
+        updated_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct KeeperUpdated {
+        old_keeper: Option<AccountId>,
+        new_keeper: Option<AccountId>,
+        updated_by: AccountId,
+    }
+
     #[ink(event)]
     pub struct EmergencyPause {
         paused: bool,
@@ -268,6 +530,15 @@ mod portfolio {
         reason: String,
     }
 
+    #[ink(event)]
+    pub struct PauseFlagsUpdated {
+        old_flags: u8,
+        new_flags: u8,
+        paused_by: AccountId,
+        timestamp: u64,
+        reason: String,
+    }
+
     #[ink(event)]
     pub struct OperationFailed {
         operation: String,
@@ -278,27 +549,66 @@ mod portfolio {
 
     // ===== CONSTANTS =====
 
+    /// Default staleness window for cached values: 1 hour in milliseconds
+    const DEFAULT_CACHE_STALENESS_MS: u64 = 3_600_000;
+
+    /// Shortest allowed staleness window: 1 minute in milliseconds
+    const MIN_CACHE_STALENESS_MS: u64 = 60_000;
+
+    /// Longest allowed staleness window: 1 day in milliseconds
+    const MAX_CACHE_STALENESS_MS: u64 = 86_400_000;
+
+    /// Default staleness window for the index value: 1 hour in milliseconds
+    const DEFAULT_INDEX_STALENESS_MS: u64 = 3_600_000;
+
+    /// Shortest allowed index staleness window: 1 minute in milliseconds
+    const MIN_INDEX_STALENESS_MS: u64 = 60_000;
+
+    /// Longest allowed index staleness window: 1 day in milliseconds
+    const MAX_INDEX_STALENESS_MS: u64 = 86_400_000;
+
     /// Default maximum tokens portfolio can hold
     const DEFAULT_MAX_TOKENS: u32 = 50;
 
     /// Default rebalancing threshold (5%)
     const DEFAULT_REBALANCE_THRESHOLD_BP: u32 = 500;
 
+    /// Default minimum time between rebalances: 1 hour in milliseconds
+    const DEFAULT_MIN_REBALANCE_INTERVAL_MS: u64 = 3_600_000;
+
     /// Default maximum single position (20% of portfolio)
     const DEFAULT_MAX_SINGLE_POSITION_BP: u32 = 2000;
 
     /// Default maximum slippage tolerance (2%)
     const DEFAULT_MAX_SLIPPAGE_BP: u32 = 200;
 
-    /// Index base value: $100 in plancks (assuming 1 DOT = 10^10 plancks)
+    /// Default index base value: $100 in plancks (assuming 1 DOT = 10^10 plancks)
     const INDEX_BASE_VALUE: u128 = 100_000_000_000; // $100
 
+    /// One W3PI index token in its smallest unit (10 decimals)
+    const INDEX_TOKEN_UNIT: u128 = 10_000_000_000;
+
     /// Minimum portfolio value: $1000 in plancks
     const MIN_PORTFOLIO_VALUE: u128 = 1_000_000_000_000; // $1000
 
     /// Default minimum liquidity buffer: $100 in USDC
     const DEFAULT_MIN_LIQUIDITY_BUFFER: u128 = 100_000_000_000; // $100
 
+    /// Plancks per DOT (10 decimals)
+    const PLANCKS_PER_DOT: u128 = 10_000_000_000;
+
+    /// One whole USD in the oracle's 9-decimal format
+    const USD_UNIT: u128 = 1_000_000_000;
+
+    /// Maximum number of holdings returned per page by paged queries
+    const MAX_PAGE_SIZE: u32 = 20;
+
+    /// One day in milliseconds
+    const MS_PER_DAY: u64 = 86_400_000;
+
+    /// Minimum track record before performance is annualized
+    const MIN_ANNUALIZATION_DAYS: u64 = 30;
+
     // ===== IMPLEMENTATION =====
 
     impl Default for Portfolio {
@@ -311,6 +621,27 @@ mod portfolio {
         /// Constructor - Initialize empty portfolio
         #[ink(constructor)]
         pub fn new() -> Self {
+            Self::init(INDEX_BASE_VALUE)
+        }
+
+        /// Create a portfolio whose index starts at a custom base value (in plancks)
+        #[ink(constructor)]
+        pub fn new_with_base_value(base_value: u128) -> Result<Self, Error> {
+            if base_value == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            Ok(Self::init(base_value))
+        }
+
+        /// Create a portfolio whose index starts at a custom base value (alias of `new_with_base_value`)
+        #[ink(constructor)]
+        pub fn new_with_base(index_base_value: u128) -> Result<Self, Error> {
+            Self::new_with_base_value(index_base_value)
+        }
+
+        /// Shared constructor logic
+        fn init(base_value: u128) -> Self {
             let caller = Self::env().caller();
             let timestamp = Self::env().block_timestamp();
 
@@ -324,19 +655,32 @@ mod portfolio {
                 holdings: Mapping::default(),
                 held_token_ids: Vec::new(),
                 total_tokens_held: 0,
+                flagged_for_removal: Vec::new(),
 
                 // Index base value system
-                index_base_value: INDEX_BASE_VALUE,
+                index_base_value: base_value,
                 base_portfolio_value: 0, // Will be set when first tokens are added
-                current_index_value: INDEX_BASE_VALUE,
+                current_index_value: base_value,
                 last_index_update: timestamp,
                 index_tracking_enabled: false, // Enable after initialization
+                cached_total_value: 0,
+                cache_timestamp: 0,
+                cache_staleness_threshold: DEFAULT_CACHE_STALENESS_MS,
+                index_staleness_threshold_ms: DEFAULT_INDEX_STALENESS_MS,
+                require_fresh_prices: false,
+                last_dot_usd_price: 0,
+                last_dot_usd_update: 0,
+                keeper: None,
 
                 // Fee system
                 fee_config: FeeConfiguration::default(),
                 collected_fees: Mapping::default(),
                 last_streaming_fee: Mapping::default(),
+                role_members: Mapping::default(),
+                reentrancy_guard: ReentrancyGuard::new(),
                 fee_beneficiaries: Mapping::default(),
+                fee_beneficiary_list: Vec::new(),
+                total_beneficiary_share_bp: 0,
                 total_fees_collected: 0,
 
                 // External contract references
@@ -344,13 +688,17 @@ mod portfolio {
                 token_contract: None,
                 dex_contract: None,
                 oracle_contract: None,
+                usdc_contract: None,
 
                 // Portfolio management
                 max_tokens: DEFAULT_MAX_TOKENS,
                 min_portfolio_value: MIN_PORTFOLIO_VALUE,
                 last_rebalance: timestamp,
                 rebalance_threshold_bp: DEFAULT_REBALANCE_THRESHOLD_BP,
+                min_rebalance_interval_ms: DEFAULT_MIN_REBALANCE_INTERVAL_MS,
+                auto_rebalance_enabled: false,
                 emergency_paused: false,
+                pause_flags: 0,
 
                 // Liquidity & risk management
                 min_liquidity_buffer: DEFAULT_MIN_LIQUIDITY_BUFFER,
@@ -362,7 +710,7 @@ mod portfolio {
             Self::env().emit_event(PortfolioInitialized {
                 owner: caller,
                 deployment_timestamp: timestamp,
-                index_base_value: INDEX_BASE_VALUE,
+                index_base_value: base_value,
                 initial_state: PortfolioState::Active,
             });
 
@@ -379,6 +727,25 @@ mod portfolio {
             Ok(())
         }
 
+        /// Ensure caller is the owner or the keeper
+        fn ensure_owner_or_keeper(&self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && self.keeper != Some(caller) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Ensure caller has required role (or is owner)
+        fn ensure_role(&self, role: Role) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller == self.owner || self.has_role(role, caller) {
+                Ok(())
+            } else {
+                Err(Error::UnauthorizedRole)
+            }
+        }
+
         /// Ensure portfolio is in active state
         #[allow(dead_code)]
         fn ensure_active(&self) -> Result<(), Error> {
@@ -391,11 +758,79 @@ mod portfolio {
         /// Ensure portfolio is not emergency paused
         fn ensure_not_emergency_paused(&self) -> Result<(), Error> {
             if self.emergency_paused {
-                return Err(Error::InvalidParameter); // Emergency paused
+                return Err(Error::ContractPaused); // Emergency paused
+            }
+            Ok(())
+        }
+
+        /// Ensure an operation is neither emergency paused nor partially paused
+        fn ensure_not_paused_for(&self, flag: u8) -> Result<(), Error> {
+            self.ensure_not_emergency_paused()?;
+            if self.pause_flags & flag != 0 {
+                return Err(Error::ContractPaused); // Operation paused
+            }
+            Ok(())
+        }
+
+        /// Ensure holdings changes are allowed
+        fn ensure_trades_allowed(&self) -> Result<(), Error> {
+            self.ensure_not_paused_for(PauseFlags::TRADES)
+        }
+
+        /// Ensure deposits are allowed
+        fn ensure_deposits_allowed(&self) -> Result<(), Error> {
+            self.ensure_not_paused_for(PauseFlags::DEPOSITS)
+        }
+
+        /// Ensure DEX rebalancing is allowed
+        fn ensure_rebalancing_allowed(&self) -> Result<(), Error> {
+            self.ensure_not_paused_for(PauseFlags::REBALANCING)
+        }
+
+        // ===== ROLE MANAGEMENT =====
+
+        /// Grant a role to an account (owner only)
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if account == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
             }
+
+            self.role_members.insert((role, account), &true);
+
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                granted_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a role from an account (owner only)
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.role_members.remove((role, account));
+
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                revoked_by: self.env().caller(),
+            });
+
             Ok(())
         }
 
+        /// Check if an account has a specific role
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.role_members.get((role, account)).unwrap_or(false)
+        }
+
         // ===== BASIC GETTERS =====
 
         /// Get portfolio owner
@@ -471,10 +906,10 @@ mod portfolio {
             Ok(())
         }
 
-        /// Emergency pause all operations (owner only)
+        /// Emergency pause all operations (owner or EmergencyController)
         #[ink(message)]
         pub fn emergency_pause(&mut self, reason: String) -> Result<(), Error> {
-            self.ensure_owner()?;
+            self.ensure_role(Role::EmergencyController)?;
 
             self.emergency_paused = true;
             self.state = PortfolioState::Emergency;
@@ -489,10 +924,10 @@ mod portfolio {
             Ok(())
         }
 
-        /// Resume operations after emergency pause (owner only)
+        /// Resume operations after emergency pause (owner or EmergencyController)
         #[ink(message)]
         pub fn resume_operations(&mut self, reason: String) -> Result<(), Error> {
-            self.ensure_owner()?;
+            self.ensure_role(Role::EmergencyController)?;
 
             self.emergency_paused = false;
             self.state = PortfolioState::Active;
@@ -507,6 +942,36 @@ mod portfolio {
             Ok(())
         }
 
+        /// Set partial pause flags (owner only)
+        /// Redemptions and emergency withdrawals are never blocked by these flags.
+        #[ink(message)]
+        pub fn set_pause_flags(&mut self, flags: u8, reason: String) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if flags & !PauseFlags::ALL != 0 {
+                return Err(Error::InvalidParameter); // Unknown flag bits
+            }
+
+            let old_flags = self.pause_flags;
+            self.pause_flags = flags;
+
+            self.env().emit_event(PauseFlagsUpdated {
+                old_flags,
+                new_flags: flags,
+                paused_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+                reason,
+            });
+
+            Ok(())
+        }
+
+        /// Get partial pause flags
+        #[ink(message)]
+        pub fn get_pause_flags(&self) -> u8 {
+            self.pause_flags
+        }
+
         /// Update fee configuration (owner only)
         #[ink(message)]
         pub fn set_fee_config(&mut self, new_config: FeeConfiguration) -> Result<(), Error> {
@@ -611,6 +1076,25 @@ mod portfolio {
             Ok(())
         }
 
+        /// Set USDC contract address (owner only)
+        #[ink(message)]
+        pub fn set_usdc_contract(&mut self, usdc: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let old_address = self.usdc_contract;
+            self.usdc_contract = Some(usdc);
+
+            self.env().emit_event(ContractReferenceUpdated {
+                contract_type: String::from("usdc"),
+                old_address,
+                new_address: usdc,
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(())
+        }
+
         /// Get contract references
         #[ink(message)]
         pub fn get_registry_contract(&self) -> Option<AccountId> {
@@ -632,9 +1116,14 @@ mod portfolio {
             self.oracle_contract
         }
 
+        #[ink(message)]
+        pub fn get_usdc_contract(&self) -> Option<AccountId> {
+            self.usdc_contract
+        }
+
         // ===== PHASE 2: HOLDINGS MANAGEMENT =====
 
-        /// Add a new token holding to the portfolio (owner only)
+        /// Add a new token holding to the portfolio (owner or TokenManager)
         #[ink(message)]
         pub fn add_token_holding(
             &mut self,
@@ -642,18 +1131,18 @@ mod portfolio {
             amount: u128,
             target_weight_bp: u32,
         ) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.ensure_not_emergency_paused()?;
+            self.ensure_role(Role::TokenManager)?;
+            self.ensure_trades_allowed()?;
 
             // Validate inputs
             if amount == 0 {
                 self.emit_operation_failed("add_token_holding", "Amount cannot be zero");
-                return Err(Error::InvalidParameter);
+                return Err(Error::AmountZero);
             }
 
             if target_weight_bp > 10000 {
                 self.emit_operation_failed("add_token_holding", "Target weight cannot exceed 100%");
-                return Err(Error::InvalidParameter);
+                return Err(Error::InvalidWeight);
             }
 
             // Check if we already hold this token
@@ -665,7 +1154,7 @@ mod portfolio {
             // Check maximum tokens limit
             if self.total_tokens_held >= self.max_tokens {
                 self.emit_operation_failed("add_token_holding", "Maximum tokens limit reached");
-                return Err(Error::InvalidParameter);
+                return Err(Error::MaxTokensReached);
             }
 
             // Check total weight allocation doesn't exceed 100%
@@ -676,7 +1165,7 @@ mod portfolio {
                     "add_token_holding",
                     "Total target weight would exceed 100%",
                 );
-                return Err(Error::InvalidParameter);
+                return Err(Error::WeightExceeded);
             }
 
             let timestamp = self.env().block_timestamp();
@@ -696,6 +1185,7 @@ mod portfolio {
 
             // Trigger index update
             self.trigger_index_update();
+            self.check_auto_rebalance();
 
             // Emit event
             self.env().emit_event(TokenHoldingAdded {
@@ -709,7 +1199,7 @@ mod portfolio {
             Ok(())
         }
 
-        /// Update an existing token holding (owner only)
+        /// Update an existing token holding (owner or TokenUpdater)
         #[ink(message)]
         pub fn update_token_holding(
             &mut self,
@@ -717,8 +1207,8 @@ mod portfolio {
             new_amount: u128,
             new_target_weight_bp: u32,
         ) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.ensure_not_emergency_paused()?;
+            self.ensure_role(Role::TokenUpdater)?;
+            self.ensure_trades_allowed()?;
 
             // Validate target weight
             if new_target_weight_bp > 10000 {
@@ -726,7 +1216,7 @@ mod portfolio {
                     "update_token_holding",
                     "Target weight cannot exceed 100%",
                 );
-                return Err(Error::InvalidParameter);
+                return Err(Error::InvalidWeight);
             }
 
             // Get existing holding
@@ -758,7 +1248,7 @@ mod portfolio {
                     "update_token_holding",
                     "Total target weight would exceed 100%",
                 );
-                return Err(Error::InvalidParameter);
+                return Err(Error::WeightExceeded);
             }
 
             // Store old values for event
@@ -775,6 +1265,7 @@ mod portfolio {
 
             // Trigger index update
             self.trigger_index_update();
+            self.check_auto_rebalance();
 
             // Emit event
             self.env().emit_event(TokenHoldingUpdated {
@@ -790,32 +1281,78 @@ mod portfolio {
             Ok(())
         }
 
-        /// Remove a token holding from the portfolio (owner only)
+        /// Reduce a holding's amount without changing its target weight
+        /// The holding is kept at zero so its weight target is preserved. Returns the new amount.
         #[ink(message)]
-        pub fn remove_token_holding(&mut self, token_id: u32) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.ensure_not_emergency_paused()?;
+        pub fn reduce_holding_amount(
+            &mut self,
+            token_id: u32,
+            amount_to_remove: u128,
+        ) -> Result<u128, Error> {
+            self.ensure_role(Role::TokenUpdater)?;
+            self.ensure_trades_allowed()?;
 
-            // Get existing holding
-            let holding = self.holdings.get(token_id).ok_or_else(|| {
-                self.emit_operation_failed("remove_token_holding", "Token not found");
+            if amount_to_remove == 0 {
+                return Err(Error::AmountZero);
+            }
+
+            let mut holding = self.holdings.get(token_id).ok_or_else(|| {
+                self.emit_operation_failed("reduce_holding_amount", "Token not found");
                 Error::TokenNotFound
             })?;
 
-            let final_amount = holding.amount;
-
-            // Remove from storage
-            self.holdings.remove(token_id);
+            let old_amount = holding.amount;
+            let new_amount = old_amount.saturating_sub(amount_to_remove);
 
-            // Remove from token IDs list
-            if let Some(pos) = self.held_token_ids.iter().position(|&x| x == token_id) {
-                self.held_token_ids.remove(pos);
-            }
+            holding.amount = new_amount;
+            holding.last_rebalance = self.env().block_timestamp();
+            self.holdings.insert(token_id, &holding);
+
+            // Trigger index update
+            self.trigger_index_update();
+            self.check_auto_rebalance();
+
+            // Emit event
+            self.env().emit_event(TokenHoldingUpdated {
+                token_id,
+                old_amount,
+                new_amount,
+                old_weight: holding.target_weight_bp,
+                new_weight: holding.target_weight_bp, // Weight unchanged
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(new_amount)
+        }
+
+        /// Remove a token holding from the portfolio (owner only)
+        #[ink(message)]
+        pub fn remove_token_holding(&mut self, token_id: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_trades_allowed()?;
+
+            // Get existing holding
+            let holding = self.holdings.get(token_id).ok_or_else(|| {
+                self.emit_operation_failed("remove_token_holding", "Token not found");
+                Error::TokenNotFound
+            })?;
+
+            let final_amount = holding.amount;
+
+            // Remove from storage
+            self.holdings.remove(token_id);
+
+            // Remove from token IDs list
+            if let Some(pos) = self.held_token_ids.iter().position(|&x| x == token_id) {
+                self.held_token_ids.remove(pos);
+            }
 
             self.total_tokens_held = self.total_tokens_held.saturating_sub(1);
 
             // Trigger index update
             self.trigger_index_update();
+            self.check_auto_rebalance();
 
             // Emit event
             self.env().emit_event(TokenHoldingRemoved {
@@ -840,21 +1377,28 @@ mod portfolio {
             self.holdings.contains(token_id)
         }
 
-        /// Get complete portfolio composition
+        /// Get complete portfolio composition valued at Registry market prices
         #[ink(message)]
         pub fn get_portfolio_composition(&self) -> PortfolioComposition {
             let mut holdings_vec = Vec::new();
             let mut total_value = 0u128;
 
-            // Collect all holdings
+            // Collect all holdings valued at Registry market prices
             for token_id in &self.held_token_ids {
                 if let Some(holding) = self.holdings.get(*token_id) {
-                    // For now, use amount as value (will be replaced with actual value calculation in later phases)
-                    total_value = total_value.saturating_add(holding.amount);
+                    // A failed price lookup counts as zero value instead of aborting
+                    let token_value = match self.call_registry_get_token_data(*token_id) {
+                        Ok(token_data) => holding.amount.checked_mul(token_data.price).unwrap_or(0),
+                        Err(_) => 0,
+                    };
+                    total_value = total_value.saturating_add(token_value);
                     holdings_vec.push((*token_id, holding));
                 }
             }
 
+            // Add USDC balance to total value
+            total_value = total_value.saturating_add(self.usdc_balance);
+
             PortfolioComposition {
                 total_tokens: self.total_tokens_held,
                 total_value,
@@ -930,14 +1474,14 @@ mod portfolio {
 
         // ===== BATCH OPERATIONS =====
 
-        /// Add multiple token holdings in a single transaction (owner only)
+        /// Add multiple token holdings in a single transaction (owner or TokenManager)
         #[ink(message)]
         pub fn add_multiple_holdings(
             &mut self,
             holdings_data: Vec<(u32, u128, u32)>, // (token_id, amount, target_weight_bp)
         ) -> Result<u32, Error> {
-            self.ensure_owner()?;
-            self.ensure_not_emergency_paused()?;
+            self.ensure_role(Role::TokenManager)?;
+            self.ensure_trades_allowed()?;
 
             if holdings_data.is_empty() {
                 return Err(Error::InvalidParameter);
@@ -950,7 +1494,7 @@ mod portfolio {
                     "add_multiple_holdings",
                     "Would exceed maximum tokens limit",
                 );
-                return Err(Error::InvalidParameter);
+                return Err(Error::MaxTokensReached);
             }
 
             // Calculate total weight for validation
@@ -981,7 +1525,7 @@ mod portfolio {
                     "add_multiple_holdings",
                     "Total weight would exceed 100%",
                 );
-                return Err(Error::InvalidParameter);
+                return Err(Error::WeightExceeded);
             }
 
             let timestamp = self.env().block_timestamp();
@@ -1011,17 +1555,19 @@ mod portfolio {
                 });
             }
 
+            self.check_auto_rebalance();
+
             Ok(added_count)
         }
 
-        /// Update multiple token amounts in a single transaction (owner only)
+        /// Update multiple token amounts in a single transaction (owner or TokenUpdater)
         #[ink(message)]
         pub fn update_multiple_amounts(
             &mut self,
             updates: Vec<(u32, u128)>, // (token_id, new_amount)
         ) -> Result<u32, Error> {
-            self.ensure_owner()?;
-            self.ensure_not_emergency_paused()?;
+            self.ensure_role(Role::TokenUpdater)?;
+            self.ensure_trades_allowed()?;
 
             if updates.is_empty() {
                 return Err(Error::InvalidParameter);
@@ -1052,9 +1598,325 @@ mod portfolio {
                 }
             }
 
+            self.check_auto_rebalance();
+
             Ok(updated_count)
         }
 
+        /// Assign every held token the same target weight (owner only)
+        /// The rounding remainder goes to the first held token so weights sum to 10000.
+        /// Returns: the per-token weight before the remainder
+        #[ink(message)]
+        pub fn set_equal_weights(&mut self) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            self.ensure_trades_allowed()?;
+
+            if self.held_token_ids.is_empty() {
+                self.emit_operation_failed("set_equal_weights", "No holdings");
+                return Err(Error::InvalidParameter);
+            }
+
+            let token_count = self.held_token_ids.len() as u32;
+            let per_token_weight = 10000_u32
+                .checked_div(token_count)
+                .ok_or(Error::InvalidParameter)?;
+            let remainder = 10000_u32.saturating_sub(per_token_weight.saturating_mul(token_count));
+
+            let weights: Vec<(u32, u32)> = self
+                .held_token_ids
+                .iter()
+                .enumerate()
+                .map(|(index, token_id)| {
+                    if index == 0 {
+                        (*token_id, per_token_weight.saturating_add(remainder))
+                    } else {
+                        (*token_id, per_token_weight)
+                    }
+                })
+                .collect();
+            self.apply_target_weights(&weights);
+
+            Ok(per_token_weight)
+        }
+
+        /// Assign target weights by share of total market cap (owner only)
+        /// No token exceeds `max_single_position_bp`; excess weight is redistributed
+        /// proportionally across the remaining tokens.
+        /// Returns: the number of tokens weighted
+        #[ink(message)]
+        pub fn set_market_cap_weights(&mut self) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            self.ensure_trades_allowed()?;
+
+            if self.held_token_ids.is_empty() {
+                self.emit_operation_failed("set_market_cap_weights", "No holdings");
+                return Err(Error::InvalidParameter);
+            }
+
+            let mut market_caps = Vec::new();
+            for token_id in &self.held_token_ids {
+                let market_cap = match self.call_registry_get_token_data(*token_id) {
+                    Ok(token_data) if token_data.market_cap > 0 => token_data.market_cap,
+                    _ => {
+                        self.emit_operation_failed(
+                            "set_market_cap_weights",
+                            &format!("No market cap for token {}", token_id),
+                        );
+                        return Err(Error::OracleCallFailed);
+                    }
+                };
+                market_caps.push(market_cap);
+            }
+
+            let cap_weights =
+                Self::calculate_cap_weights(&market_caps, self.max_single_position_bp)?;
+            let weights: Vec<(u32, u32)> = self
+                .held_token_ids
+                .iter()
+                .copied()
+                .zip(cap_weights)
+                .collect();
+            self.apply_target_weights(&weights);
+
+            Ok(weights.len() as u32)
+        }
+
+        /// Split 10000 bp by market cap share, capping each weight at `max_weight_bp`
+        /// Capped weight is redistributed over the uncapped tokens until none exceeds the
+        /// cap. The rounding remainder goes to the largest uncapped weight when it fits.
+        fn calculate_cap_weights(
+            market_caps: &[u128],
+            max_weight_bp: u32,
+        ) -> Result<Vec<u32>, Error> {
+            let mut weights = vec![0u32; market_caps.len()];
+            let mut capped = vec![false; market_caps.len()];
+            let mut budget_bp = 10000_u32;
+
+            loop {
+                let uncapped_total = market_caps
+                    .iter()
+                    .zip(&capped)
+                    .filter(|(_, is_capped)| !**is_capped)
+                    .try_fold(0u128, |total, (cap, _)| total.checked_add(*cap))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                if uncapped_total == 0 {
+                    break;
+                }
+
+                let mut newly_capped = false;
+                for (index, cap) in market_caps.iter().enumerate() {
+                    if capped[index] {
+                        continue;
+                    }
+
+                    let share_bp = cap
+                        .checked_mul(budget_bp as u128)
+                        .ok_or(Error::ArithmeticOverflow)?
+                        .checked_div(uncapped_total)
+                        .ok_or(Error::InvalidParameter)?;
+                    if share_bp > max_weight_bp as u128 {
+                        weights[index] = max_weight_bp;
+                        capped[index] = true;
+                        newly_capped = true;
+                    } else {
+                        weights[index] = share_bp as u32;
+                    }
+                }
+
+                if !newly_capped {
+                    break;
+                }
+
+                // Re-split what is left over the remaining uncapped tokens
+                let capped_total = weights
+                    .iter()
+                    .zip(&capped)
+                    .filter(|(_, is_capped)| **is_capped)
+                    .fold(0u32, |total, (weight, _)| total.saturating_add(*weight));
+                budget_bp = 10000_u32.saturating_sub(capped_total);
+            }
+
+            let assigned = weights
+                .iter()
+                .fold(0u32, |total, weight| total.saturating_add(*weight));
+            let remainder = 10000_u32.saturating_sub(assigned);
+            // Reversed so ties resolve to the first token, as in set_equal_weights
+            let largest_uncapped = (0..weights.len())
+                .rev()
+                .filter(|index| !capped[*index])
+                .max_by_key(|index| weights[*index]);
+            if let Some(index) = largest_uncapped {
+                weights[index] = weights[index].saturating_add(remainder).min(max_weight_bp);
+            }
+
+            Ok(weights)
+        }
+
+        /// Write new target weights for held tokens and refresh the index
+        /// Emits `TokenHoldingUpdated` for every weight that changed.
+        fn apply_target_weights(&mut self, weights: &[(u32, u32)]) {
+            let timestamp = self.env().block_timestamp();
+
+            for (token_id, new_weight) in weights {
+                if let Some(mut holding) = self.holdings.get(*token_id) {
+                    let old_weight = holding.target_weight_bp;
+                    if old_weight == *new_weight {
+                        continue;
+                    }
+
+                    holding.target_weight_bp = *new_weight;
+                    self.holdings.insert(*token_id, &holding);
+
+                    self.env().emit_event(TokenHoldingUpdated {
+                        token_id: *token_id,
+                        old_amount: holding.amount,
+                        new_amount: holding.amount, // Amount unchanged
+                        old_weight,
+                        new_weight: *new_weight,
+                        updated_by: self.env().caller(),
+                        timestamp,
+                    });
+                }
+            }
+
+            self.trigger_index_update();
+            self.check_auto_rebalance();
+        }
+
+        /// Remove multiple token holdings in a single transaction (owner only)
+        /// Token IDs that are not held are skipped.
+        #[ink(message)]
+        pub fn remove_multiple_holdings(&mut self, token_ids: Vec<u32>) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            self.ensure_trades_allowed()?;
+
+            if token_ids.is_empty() {
+                return Err(Error::InvalidParameter);
+            }
+
+            let timestamp = self.env().block_timestamp();
+            let mut removed_count = 0u32;
+
+            for token_id in token_ids {
+                if let Some(holding) = self.holdings.get(token_id) {
+                    self.holdings.remove(token_id);
+
+                    if let Some(pos) = self.held_token_ids.iter().position(|&x| x == token_id) {
+                        self.held_token_ids.remove(pos);
+                    }
+
+                    self.total_tokens_held = self.total_tokens_held.saturating_sub(1);
+                    removed_count = removed_count.saturating_add(1);
+
+                    // Emit event
+                    self.env().emit_event(TokenHoldingRemoved {
+                        token_id,
+                        final_amount: holding.amount,
+                        removed_by: self.env().caller(),
+                        timestamp,
+                    });
+                }
+            }
+
+            // Trigger a single index update for the whole batch
+            if removed_count > 0 {
+                self.trigger_index_update();
+                self.check_auto_rebalance();
+            }
+
+            Ok(removed_count)
+        }
+
+        // ===== MIGRATION =====
+
+        /// Export holdings in `held_token_ids` order for migration to a new contract
+        /// An empty page is returned for `limit == 0` or an offset past the end.
+        #[ink(message)]
+        pub fn export_holdings(&self, offset: u32, limit: u32) -> Vec<(u32, TokenHolding)> {
+            let (start, end) = match self.page_bounds(offset, limit) {
+                Ok((start, end, _)) => (start, end),
+                Err(_) => return Vec::new(),
+            };
+
+            self.held_token_ids[start..end]
+                .iter()
+                .filter_map(|token_id| {
+                    self.holdings
+                        .get(*token_id)
+                        .map(|holding| (*token_id, holding))
+                })
+                .collect()
+        }
+
+        /// Bulk-load holdings exported from a previous contract (owner only)
+        /// Only allowed before the base portfolio value is initialized. Holdings keep
+        /// their `last_rebalance` and `fees_collected` values.
+        #[ink(message)]
+        pub fn import_holdings(&mut self, entries: Vec<(u32, TokenHolding)>) -> Result<u32, Error> {
+            self.ensure_owner()?;
+
+            if self.base_portfolio_value != 0 {
+                self.emit_operation_failed(
+                    "import_holdings",
+                    "Base portfolio value already initialized",
+                );
+                return Err(Error::InvalidParameter);
+            }
+
+            if entries.is_empty() {
+                return Err(Error::InvalidParameter);
+            }
+
+            let new_token_count = entries.len() as u32;
+            if self.total_tokens_held.saturating_add(new_token_count) > self.max_tokens {
+                self.emit_operation_failed("import_holdings", "Would exceed maximum tokens limit");
+                return Err(Error::MaxTokensReached);
+            }
+
+            let mut total_weight = self.calculate_total_target_weight();
+            for (index, (token_id, holding)) in entries.iter().enumerate() {
+                if self.holdings.contains(*token_id)
+                    || entries[..index]
+                        .iter()
+                        .any(|(earlier, _)| earlier == token_id)
+                {
+                    self.emit_operation_failed("import_holdings", "Duplicate token");
+                    return Err(Error::TokenAlreadyExists);
+                }
+
+                if holding.target_weight_bp > 10000 {
+                    return Err(Error::InvalidWeight);
+                }
+                total_weight = total_weight.saturating_add(holding.target_weight_bp);
+            }
+
+            if total_weight > 10000 {
+                self.emit_operation_failed("import_holdings", "Total weight would exceed 100%");
+                return Err(Error::WeightExceeded);
+            }
+
+            let timestamp = self.env().block_timestamp();
+            for (token_id, holding) in &entries {
+                self.holdings.insert(*token_id, holding);
+                self.held_token_ids.push(*token_id);
+                self.total_tokens_held = self.total_tokens_held.saturating_add(1);
+                if holding.fees_collected > 0 {
+                    self.collected_fees
+                        .insert(*token_id, &holding.fees_collected);
+                }
+
+                self.env().emit_event(TokenHoldingAdded {
+                    token_id: *token_id,
+                    amount: holding.amount,
+                    target_weight_bp: holding.target_weight_bp,
+                    added_by: self.env().caller(),
+                    timestamp,
+                });
+            }
+
+            Ok(new_token_count)
+        }
+
         // ===== VALIDATION & LIMITS MANAGEMENT =====
 
         /// Set maximum tokens limit (owner only)
@@ -1119,8 +1981,9 @@ mod portfolio {
 
         /// Initialize base portfolio value after first tokens are added (owner only)
         /// This sets the immutable baseline for performance tracking
+        /// Fails with `OracleCallFailed` if any held token price is stale, unless `force` is set.
         #[ink(message)]
-        pub fn initialize_base_portfolio_value(&mut self) -> Result<(), Error> {
+        pub fn initialize_base_portfolio_value(&mut self, force: bool) -> Result<(), Error> {
             self.ensure_owner()?;
 
             // Can only initialize once
@@ -1141,6 +2004,17 @@ mod portfolio {
                 return Err(Error::InvalidParameter);
             }
 
+            // A stale baseline would skew every future performance number
+            let stale_tokens = self.get_stale_valuation_tokens()?;
+            let staleness_overridden = !stale_tokens.is_empty();
+            if staleness_overridden && !force {
+                self.emit_operation_failed(
+                    "initialize_base_portfolio_value",
+                    &format!("Stale prices for tokens {:?}", stale_tokens),
+                );
+                return Err(Error::PriceStale);
+            }
+
             // Calculate current portfolio value as baseline
             let total_value = self.calculate_total_portfolio_value()?;
 
@@ -1154,9 +2028,10 @@ mod portfolio {
 
             // Set immutable baseline values
             self.base_portfolio_value = total_value;
-            self.current_index_value = self.index_base_value; // Start at $100
+            self.current_index_value = self.index_base_value; // Start at base value
             self.index_tracking_enabled = true;
             self.last_index_update = self.env().block_timestamp();
+            self.update_value_cache(total_value);
 
             // Emit initialization event
             self.env().emit_event(BasePortfolioValueSet {
@@ -1164,6 +2039,7 @@ mod portfolio {
                 base_portfolio_value: total_value,
                 deployment_timestamp: self.deployment_timestamp,
                 set_by: self.env().caller(),
+                staleness_overridden,
             });
 
             Ok(())
@@ -1179,6 +2055,11 @@ mod portfolio {
 
             let current_portfolio_value = self.calculate_total_portfolio_value()?;
 
+            self.index_value_from_portfolio_value(current_portfolio_value)
+        }
+
+        /// Convert a total portfolio value into an index value relative to the baseline
+        fn index_value_from_portfolio_value(&self, portfolio_value: u128) -> Result<u128, Error> {
             // Prevent division by zero
             if self.base_portfolio_value == 0 {
                 return Ok(self.index_base_value);
@@ -1186,9 +2067,9 @@ mod portfolio {
 
             // Calculate: (current_value / base_value) × base_index_value
             // Use checked arithmetic to prevent overflow
-            let index_value = current_portfolio_value
+            let index_value = portfolio_value
                 .checked_mul(self.index_base_value)
-                .ok_or(Error::InvalidParameter)?
+                .ok_or(Error::ArithmeticOverflow)?
                 .checked_div(self.base_portfolio_value)
                 .ok_or(Error::InvalidParameter)?;
 
@@ -1201,27 +2082,75 @@ mod portfolio {
             self.current_index_value
         }
 
-        /// Update cached index value with real-time calculation (owner only)
+        /// Update cached index value with real-time calculation (owner or TokenUpdater)
         #[ink(message)]
         pub fn update_index_value(&mut self) -> Result<u128, Error> {
+            self.ensure_role(Role::TokenUpdater)?;
+
+            self.perform_index_update()
+        }
+
+        /// Update the index value from automation (owner or keeper)
+        /// Only allowed once the index value is stale, so the keeper cannot spam updates.
+        #[ink(message)]
+        pub fn keeper_update_index(&mut self) -> Result<u128, Error> {
+            self.ensure_owner_or_keeper()?;
+
+            if !self.is_index_value_stale() {
+                self.emit_operation_failed("keeper_update_index", "Index value is not stale");
+                return Err(Error::InvalidParameter);
+            }
+
+            self.perform_index_update()
+        }
+
+        /// Set or clear the keeper allowed to call `keeper_update_index` (owner only)
+        #[ink(message)]
+        pub fn set_keeper(&mut self, keeper: Option<AccountId>) -> Result<(), Error> {
             self.ensure_owner()?;
 
+            let old_keeper = self.keeper;
+            self.keeper = keeper;
+
+            self.env().emit_event(KeeperUpdated {
+                old_keeper,
+                new_keeper: keeper,
+                updated_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Get the current keeper, if any
+        #[ink(message)]
+        pub fn get_keeper(&self) -> Option<AccountId> {
+            self.keeper
+        }
+
+        /// Recalculate the index value, refresh the caches and emit `IndexValueUpdated`
+        fn perform_index_update(&mut self) -> Result<u128, Error> {
             if !self.index_tracking_enabled {
                 return Ok(self.index_base_value);
             }
 
             let old_value = self.current_index_value;
-            let new_value = self.calculate_current_index_value()?;
+
+            // Single pass over holdings, shared by the index value and the value cache
+            let total_portfolio_value = self.calculate_total_portfolio_value()?;
+            let new_value = if self.base_portfolio_value == 0 {
+                self.index_base_value
+            } else {
+                self.index_value_from_portfolio_value(total_portfolio_value)?
+            };
 
             self.current_index_value = new_value;
             self.last_index_update = self.env().block_timestamp();
+            self.update_value_cache(total_portfolio_value);
+            self.refresh_dot_usd_rate();
 
             // Calculate performance in basis points
             let performance_bp = self.calculate_performance_bp(new_value)?;
 
-            // Get current portfolio value for event
-            let total_portfolio_value = self.calculate_total_portfolio_value().unwrap_or(0);
-
             // Emit update event
             self.env().emit_event(IndexValueUpdated {
                 old_value,
@@ -1234,13 +2163,46 @@ mod portfolio {
             Ok(new_value)
         }
 
-        /// Get index performance as basis points relative to $100 baseline
+        /// Get index performance as basis points relative to the base value
         /// Returns: +2500 for +25%, -1500 for -15%, etc.
         #[ink(message)]
         pub fn get_index_performance(&self) -> Result<i32, Error> {
             self.calculate_performance_bp(self.current_index_value)
         }
 
+        /// Get index performance annualized over the time since inception, in basis points
+        /// Windows shorter than 30 days return the raw cumulative performance.
+        #[ink(message)]
+        pub fn get_annualized_performance_bp(&self) -> Result<i32, Error> {
+            let cumulative_bp = self.calculate_performance_bp(self.current_index_value)?;
+
+            if self.get_days_since_inception() < MIN_ANNUALIZATION_DAYS {
+                return Ok(cumulative_bp);
+            }
+
+            let elapsed_ms = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(self.deployment_timestamp);
+
+            // Simple annualization: cumulative × (1 year / elapsed)
+            let annualized = (cumulative_bp as i128)
+                .saturating_mul((MS_PER_DAY as i128).saturating_mul(365))
+                .checked_div(elapsed_ms as i128)
+                .ok_or(Error::InvalidParameter)?;
+
+            Ok(annualized.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+        }
+
+        /// Get whole days elapsed since deployment
+        #[ink(message)]
+        pub fn get_days_since_inception(&self) -> u64 {
+            self.env()
+                .block_timestamp()
+                .saturating_sub(self.deployment_timestamp)
+                / MS_PER_DAY
+        }
+
         /// Get real-time index performance (recalculates current value)
         #[ink(message)]
         pub fn get_realtime_index_performance(&self) -> Result<i32, Error> {
@@ -1252,16 +2214,23 @@ mod portfolio {
         #[ink(message)]
         pub fn get_index_base_metrics(&self) -> (u128, u128, u64, bool) {
             (
-                self.index_base_value,       // $100 baseline
+                self.index_base_value,       // Index baseline (default $100)
                 self.base_portfolio_value,   // Portfolio value at initialization
                 self.deployment_timestamp,   // When contract was deployed
                 self.index_tracking_enabled, // Whether tracking is active
             )
         }
 
-        /// Get index value in USD (converted via DOT/USD oracle)
+        /// Get index value in whole USD, rounded down (converted via DOT/USD oracle)
+        /// See `get_index_value_usd_precise` for the 9-decimal value.
         #[ink(message)]
         pub fn get_index_value_usd(&self) -> Result<u128, Error> {
+            Ok(self.get_index_value_usd_precise()? / USD_UNIT)
+        }
+
+        /// Get index value in USD with 9 decimals (the oracle's format)
+        #[ink(message)]
+        pub fn get_index_value_usd_precise(&self) -> Result<u128, Error> {
             // Get current index value in plancks
             let index_value_plancks = self.current_index_value;
 
@@ -1269,11 +2238,65 @@ mod portfolio {
             self.convert_plancks_to_usd(index_value_plancks)
         }
 
-        /// Get real-time index value in USD
+        /// Get real-time index value in whole USD, rounded down
         #[ink(message)]
         pub fn get_realtime_index_value_usd(&self) -> Result<u128, Error> {
             let current_value = self.calculate_current_index_value()?;
-            self.convert_plancks_to_usd(current_value)
+            Ok(self.convert_plancks_to_usd(current_value)? / USD_UNIT)
+        }
+
+        /// Get net asset value per whole W3PI share in plancks
+        /// Uses the portfolio value cached at the last index update when available.
+        #[ink(message)]
+        pub fn get_nav_per_share(&self) -> Result<u128, Error> {
+            let portfolio_value = if self.cache_timestamp > 0 {
+                self.cached_total_value
+            } else {
+                self.calculate_total_portfolio_value()
+                    .map_err(|_| Error::OracleCallFailed)?
+            };
+
+            self.calculate_nav_per_share(portfolio_value)
+        }
+
+        /// Get net asset value per whole W3PI share in plancks, recomputed live
+        #[ink(message)]
+        pub fn get_realtime_nav_per_share(&self) -> Result<u128, Error> {
+            let portfolio_value = self
+                .calculate_total_portfolio_value()
+                .map_err(|_| Error::OracleCallFailed)?;
+
+            self.calculate_nav_per_share(portfolio_value)
+        }
+
+        /// Divide a portfolio value by the W3PI supply, scaled to one whole share
+        fn calculate_nav_per_share(&self, portfolio_value: u128) -> Result<u128, Error> {
+            let token = self.token_contract.ok_or(Error::InvalidParameter)?;
+            let total_supply = self.call_token_total_supply(token)?;
+
+            if total_supply == 0 {
+                return Ok(0);
+            }
+
+            Self::nav_per_share(portfolio_value, total_supply)
+        }
+
+        /// Calculate `portfolio_value * INDEX_TOKEN_UNIT / total_supply` with overflow checks
+        fn nav_per_share(portfolio_value: u128, total_supply: u128) -> Result<u128, Error> {
+            portfolio_value
+                .checked_mul(INDEX_TOKEN_UNIT)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(total_supply)
+                .ok_or(Error::InvalidParameter)
+        }
+
+        /// Convert a value into whole-share units at `nav_per_share`
+        fn shares_for_value(value: u128, nav_per_share: u128) -> Result<u128, Error> {
+            value
+                .checked_mul(INDEX_TOKEN_UNIT)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(nav_per_share)
+                .ok_or(Error::InvalidParameter)
         }
 
         /// Check if index value data is stale (not updated recently)
@@ -1284,37 +2307,111 @@ mod portfolio {
             }
 
             let current_time = self.env().block_timestamp();
-            let staleness_threshold = 3_600_000u64; // 1 hour in milliseconds
 
-            current_time.saturating_sub(self.last_index_update) > staleness_threshold
+            current_time.saturating_sub(self.last_index_update) > self.index_staleness_threshold_ms
         }
 
-        /// Get time since last index update in milliseconds
+        /// Get cached total portfolio value without recalculating
+        /// Returns: (value, age_ms, is_stale)
         #[ink(message)]
-        pub fn get_index_update_age(&self) -> u64 {
-            let current_time = self.env().block_timestamp();
-            current_time.saturating_sub(self.last_index_update)
+        pub fn get_cached_portfolio_value(&self) -> (u128, u64, bool) {
+            let age = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(self.cache_timestamp);
+            let is_stale = self.cache_timestamp == 0 || age > self.cache_staleness_threshold;
+
+            (self.cached_total_value, age, is_stale)
         }
 
-        /// Force index recalculation and update (owner only)
+        /// Set the staleness window for the cached portfolio value in milliseconds
+        /// (owner only). Must be between 1 minute and 1 day.
         #[ink(message)]
-        pub fn refresh_index_value(&mut self) -> Result<(u128, i32), Error> {
+        pub fn set_cache_staleness_threshold(&mut self, threshold_ms: u64) -> Result<(), Error> {
             self.ensure_owner()?;
 
-            let new_value = self.update_index_value()?;
-            let performance = self.get_index_performance()?;
+            if !(MIN_CACHE_STALENESS_MS..=MAX_CACHE_STALENESS_MS).contains(&threshold_ms) {
+                return Err(Error::InvalidParameter);
+            }
 
-            Ok((new_value, performance))
+            let old_threshold_ms = self.cache_staleness_threshold;
+            self.cache_staleness_threshold = threshold_ms;
+
+            self.env().emit_event(StalenessThresholdUpdated {
+                old_threshold_ms,
+                new_threshold_ms: threshold_ms,
+                updated_by: self.env().caller(),
+            });
+
+            Ok(())
         }
 
-        /// Get index performance over time periods (if we had historical data)
+        /// Get the staleness window for cached values in milliseconds
         #[ink(message)]
-        pub fn get_index_summary(&self) -> Result<(u128, u128, i32, u64), Error> {
-            // Returns: (current_value, base_value, performance_bp, last_update)
-            Ok((
-                self.current_index_value,
-                self.index_base_value,
-                self.get_index_performance()?,
+        pub fn get_cache_staleness_threshold(&self) -> u64 {
+            self.cache_staleness_threshold
+        }
+
+        /// Set the staleness window for the index value in milliseconds (owner only)
+        /// Must be between 1 minute and 1 day.
+        #[ink(message)]
+        pub fn set_index_staleness_threshold(&mut self, ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if !(MIN_INDEX_STALENESS_MS..=MAX_INDEX_STALENESS_MS).contains(&ms) {
+                return Err(Error::InvalidParameter);
+            }
+
+            let old_threshold_ms = self.index_staleness_threshold_ms;
+            self.index_staleness_threshold_ms = ms;
+
+            self.env().emit_event(IndexStalenessThresholdUpdated {
+                old_threshold_ms,
+                new_threshold_ms: ms,
+                updated_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Get the staleness window for the index value in milliseconds
+        #[ink(message)]
+        pub fn get_index_staleness_threshold(&self) -> u64 {
+            self.index_staleness_threshold_ms
+        }
+
+        /// Store a freshly computed total portfolio value
+        fn update_value_cache(&mut self, total_value: u128) {
+            self.cached_total_value = total_value;
+            self.cache_timestamp = self.env().block_timestamp();
+        }
+
+        /// Get time since last index update in milliseconds
+        #[ink(message)]
+        pub fn get_index_update_age(&self) -> u64 {
+            let current_time = self.env().block_timestamp();
+            current_time.saturating_sub(self.last_index_update)
+        }
+
+        /// Force index recalculation and update (owner or TokenUpdater)
+        #[ink(message)]
+        pub fn refresh_index_value(&mut self) -> Result<(u128, i32), Error> {
+            self.ensure_role(Role::TokenUpdater)?;
+
+            let new_value = self.update_index_value()?;
+            let performance = self.get_index_performance()?;
+
+            Ok((new_value, performance))
+        }
+
+        /// Get index performance over time periods (if we had historical data)
+        #[ink(message)]
+        pub fn get_index_summary(&self) -> Result<(u128, u128, i32, u64), Error> {
+            // Returns: (current_value, base_value, performance_bp, last_update)
+            Ok((
+                self.current_index_value,
+                self.index_base_value,
+                self.get_index_performance()?,
                 self.last_index_update,
             ))
         }
@@ -1329,7 +2426,7 @@ mod portfolio {
             if enabled && self.base_portfolio_value == 0 {
                 // Auto-initialize if we have holdings
                 if self.total_tokens_held > 0 {
-                    self.initialize_base_portfolio_value()?;
+                    self.initialize_base_portfolio_value(false)?;
                 }
             }
 
@@ -1351,8 +2448,9 @@ mod portfolio {
             let current_value = self.calculate_total_portfolio_value()?;
 
             self.base_portfolio_value = current_value;
-            self.current_index_value = self.index_base_value; // Reset to $100
+            self.current_index_value = self.index_base_value; // Reset to base value
             self.last_index_update = self.env().block_timestamp();
+            self.update_value_cache(current_value);
 
             // Emit reset event
             self.env().emit_event(BasePortfolioValueSet {
@@ -1360,6 +2458,7 @@ mod portfolio {
                 base_portfolio_value: current_value,
                 deployment_timestamp: self.env().block_timestamp(), // New timestamp
                 set_by: self.env().caller(),
+                staleness_overridden: false,
             });
 
             // Log the emergency reset
@@ -1385,7 +2484,7 @@ mod portfolio {
                 let gain = current_value.saturating_sub(self.index_base_value);
                 let performance_bp = gain
                     .checked_mul(10000) // Convert to basis points
-                    .ok_or(Error::InvalidParameter)?
+                    .ok_or(Error::ArithmeticOverflow)?
                     .checked_div(self.index_base_value)
                     .ok_or(Error::InvalidParameter)?;
 
@@ -1396,7 +2495,7 @@ mod portfolio {
                 let loss = self.index_base_value.saturating_sub(current_value);
                 let performance_bp = loss
                     .checked_mul(10000) // Convert to basis points
-                    .ok_or(Error::InvalidParameter)?
+                    .ok_or(Error::ArithmeticOverflow)?
                     .checked_div(self.index_base_value)
                     .ok_or(Error::InvalidParameter)?;
 
@@ -1408,20 +2507,75 @@ mod portfolio {
         }
 
         /// Convert plancks to USD using DOT/USD oracle rate
-        /// This will be fully implemented in Phase 4 with Oracle integration
+        /// Returns USD in the oracle's 9-decimal format
         fn convert_plancks_to_usd(&self, plancks: u128) -> Result<u128, Error> {
-            // Placeholder implementation - will integrate with Oracle in Phase 4
-            // For now, assume 1 DOT = $6 USD (1 DOT = 10^10 plancks)
-            // So $1 USD = 10^10 / 6 = ~1.67 × 10^9 plancks
+            let dot_usd_price = self.get_dot_usd_rate()?;
+
+            // usd = plancks × (USD per DOT) / (plancks per DOT)
+            plancks
+                .checked_mul(dot_usd_price)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(PLANCKS_PER_DOT)
+                .ok_or(Error::InvalidParameter)
+        }
+
+        /// Get the DOT/USD price, falling back to the last known fresh price
+        fn get_dot_usd_rate(&self) -> Result<u128, Error> {
+            match self.call_oracle_get_dot_usd_price() {
+                Ok(price) => Ok(price),
+                Err(_) if self.last_dot_usd_price > 0 => {
+                    self.emit_operation_failed(
+                        "get_dot_usd_rate",
+                        &format!(
+                            "Using cached DOT/USD rate from {}",
+                            self.last_dot_usd_update
+                        ),
+                    );
+                    Ok(self.last_dot_usd_price)
+                }
+                Err(e) => Err(e),
+            }
+        }
 
-            let placeholder_usd_rate = 1_666_666_667u128; // Plancks per USD (conservative estimate)
+        /// Cache the current DOT/USD price if the oracle has a fresh one
+        fn refresh_dot_usd_rate(&mut self) {
+            if let Ok(price) = self.call_oracle_get_dot_usd_price() {
+                self.last_dot_usd_price = price;
+                self.last_dot_usd_update = self.env().block_timestamp();
+            }
+        }
 
-            if placeholder_usd_rate == 0 {
-                return Err(Error::OracleCallFailed);
+        /// Get last known DOT/USD price and when it was cached
+        /// Returns: (price, timestamp)
+        #[ink(message)]
+        pub fn get_cached_dot_usd_rate(&self) -> (u128, u64) {
+            (self.last_dot_usd_price, self.last_dot_usd_update)
+        }
+
+        /// Cross-contract call to get a fresh DOT/USD price from the Oracle
+        fn call_oracle_get_dot_usd_price(&self) -> Result<u128, Error> {
+            let oracle = self.oracle_contract.ok_or(Error::OracleCallFailed)?;
+
+            let stale_result = Self::oracle_ref(oracle)
+                .call()
+                .is_dot_price_stale()
+                .try_invoke();
+
+            match stale_result {
+                Ok(Ok(false)) => {}
+                Ok(Ok(true)) => return Err(Error::PriceStale),
+                _ => return Err(Error::OracleCallFailed),
             }
 
-            let usd_value = plancks.checked_div(placeholder_usd_rate).unwrap_or(0);
-            Ok(usd_value)
+            let price_result = Self::oracle_ref(oracle)
+                .call()
+                .get_dot_usd_price()
+                .try_invoke();
+
+            match price_result {
+                Ok(Ok(Some(price))) if price > 0 => Ok(price),
+                _ => Err(Error::OracleCallFailed),
+            }
         }
 
         // ===== INTEGRATION HOOKS FOR AUTOMATIC INDEX UPDATES =====
@@ -1446,34 +2600,16 @@ mod portfolio {
                 Error::InvalidParameter
             })?;
 
-            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(registry)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_token_data"),
-                    ))
-                    .push_arg(token_id),
-                )
-                .returns::<Result<shared::EnrichedTokenData, shared::Error>>()
+            let result = Self::registry_ref(registry)
+                .call()
+                .get_token_data(token_id)
                 .try_invoke();
 
             match result {
                 Ok(registry_result) => match registry_result {
                     Ok(data) => {
                         let data = data?; // Unwrap the inner Result
-                        Ok(EnrichedTokenData {
-                            token_contract: data.token_contract,
-                            oracle_contract: data.oracle_contract,
-                            balance: data.balance,
-                            weight_investment: data.weight_investment,
-                            tier: data.tier,
-                            market_cap: data.market_cap,
-                            market_volume: data.market_volume,
-                            price: data.price,
-                        })
+                        Ok(EnrichedTokenData::from(data))
                     }
                     Err(_) => {
                         self.emit_operation_failed(
@@ -1493,11 +2629,41 @@ mod portfolio {
             }
         }
 
-        /// Cross-contract call to get active tier from Registry
-        fn call_registry_get_active_tier(&self) -> Result<u32, Error> {
+        /// Cross-contract call to get data for several tokens from Registry in one call
+        fn call_registry_get_token_data_batch(
+            &self,
+            token_ids: Vec<u32>,
+        ) -> Result<Vec<(u32, EnrichedTokenData)>, Error> {
+            let registry = self.registry_contract.ok_or(Error::InvalidParameter)?;
+
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(registry)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("get_token_data_batch"),
+                    ))
+                    .push_arg(token_ids),
+                )
+                .returns::<Vec<(u32, shared::EnrichedTokenData)>>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(batch)) => Ok(batch
+                    .into_iter()
+                    .map(|(token_id, data)| (token_id, EnrichedTokenData::from(data)))
+                    .collect()),
+                _ => Err(Error::OracleCallFailed), // Batch selector unavailable
+            }
+        }
+
+        /// Cross-contract call to check token price staleness via Registry
+        fn call_registry_is_price_stale(&self, token_id: u32) -> Result<bool, Error> {
             let registry = self.registry_contract.ok_or_else(|| {
                 self.emit_operation_failed(
-                    "call_registry_get_active_tier",
+                    "call_registry_is_price_stale",
                     "Registry contract not set",
                 );
                 Error::InvalidParameter
@@ -1508,10 +2674,40 @@ mod portfolio {
                 .call_v1()
                 .gas_limit(0)
                 .transferred_value(0)
-                .exec_input(ink::env::call::ExecutionInput::new(
-                    ink::env::call::Selector::new(ink::selector_bytes!("get_active_tier")),
-                ))
-                .returns::<u32>() // Assuming Tier enum converts to u32
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("is_token_price_stale"),
+                    ))
+                    .push_arg(token_id),
+                )
+                .returns::<Result<bool, shared::Error>>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(Ok(is_stale))) => Ok(is_stale),
+                _ => {
+                    self.emit_operation_failed(
+                        "call_registry_is_price_stale",
+                        "Registry call failed",
+                    );
+                    Err(Error::OracleCallFailed)
+                }
+            }
+        }
+
+        /// Cross-contract call to get active tier from Registry
+        fn call_registry_get_active_tier(&self) -> Result<u32, Error> {
+            let registry = self.registry_contract.ok_or_else(|| {
+                self.emit_operation_failed(
+                    "call_registry_get_active_tier",
+                    "Registry contract not set",
+                );
+                Error::InvalidParameter
+            })?;
+
+            let result = Self::registry_ref(registry)
+                .call()
+                .get_active_tier()
                 .try_invoke();
 
             match result {
@@ -1545,18 +2741,9 @@ mod portfolio {
                 Error::InvalidParameter
             })?;
 
-            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(registry)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_tokens_by_tier"),
-                    ))
-                    .push_arg(tier),
-                )
-                .returns::<Vec<u32>>()
+            let result = Self::registry_ref(registry)
+                .call()
+                .get_tokens_by_tier(tier)
                 .try_invoke();
 
             match result {
@@ -1601,11 +2788,13 @@ mod portfolio {
             let value = holding
                 .amount
                 .checked_mul(token_data.price)
-                .ok_or(Error::InvalidParameter)?;
+                .ok_or(Error::ArithmeticOverflow)?;
             Ok(value)
         }
 
         /// Get all holdings with current market values
+        /// Issues one Registry call per holding; gas grows with the number of holdings.
+        /// Prefer `get_holdings_with_values_paged` for large portfolios.
         #[ink(message)]
         pub fn get_holdings_with_values(&self) -> Result<Vec<(u32, u128, u128)>, Error> {
             let mut holdings_with_values = Vec::new();
@@ -1628,6 +2817,51 @@ mod portfolio {
             Ok(holdings_with_values)
         }
 
+        /// Get a page of holdings with current market values
+        /// Returns: (Vec<(token_id, amount, value)>, next_offset)
+        #[ink(message)]
+        pub fn get_holdings_with_values_paged(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Result<(Vec<(u32, u128, u128)>, Option<u32>), Error> {
+            let (start, end, next_offset) = self.page_bounds(offset, limit)?;
+            let mut holdings_with_values = Vec::new();
+
+            for token_id in &self.held_token_ids[start..end] {
+                if let Some(holding) = self.holdings.get(*token_id) {
+                    let value = match self.call_registry_get_token_data(*token_id) {
+                        Ok(token_data) => holding.amount.checked_mul(token_data.price).unwrap_or(0),
+                        Err(_) => 0, // If we can't get price, include with 0 value
+                    };
+                    holdings_with_values.push((*token_id, holding.amount, value));
+                }
+            }
+
+            Ok((holdings_with_values, next_offset))
+        }
+
+        /// Resolve a page request into a slice of `held_token_ids`
+        /// Returns: (start, end, next_offset)
+        fn page_bounds(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Result<(usize, usize, Option<u32>), Error> {
+            if limit == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            let len = self.held_token_ids.len();
+            let start = (offset as usize).min(len);
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE) as usize)
+                .min(len);
+            let next_offset = if end < len { Some(end as u32) } else { None };
+
+            Ok((start, end, next_offset))
+        }
+
         /// Get active tier tokens for rebalancing decisions
         #[ink(message)]
         pub fn get_rebalancing_targets(&self) -> Result<Vec<u32>, Error> {
@@ -1657,6 +2891,95 @@ mod portfolio {
             Ok(invalid_tokens)
         }
 
+        /// Sync holdings with the Registry's active tier (owner or keeper)
+        /// Active-tier tokens not yet held are added with zero amount and an even share of
+        /// the target weight (limited by `max_tokens` and the remaining weight capacity).
+        /// Held tokens outside the active tier are flagged for removal, not removed.
+        /// Returns: (added, flagged)
+        #[ink(message)]
+        pub fn sync_holdings_with_active_tier(&mut self) -> Result<(u32, u32), Error> {
+            self.ensure_owner_or_keeper()?;
+            self.ensure_trades_allowed()?;
+
+            let targets = self.get_rebalancing_targets()?;
+            let timestamp = self.env().block_timestamp();
+
+            // Add missing active-tier tokens, up to the token limit
+            let open_slots = self.max_tokens.saturating_sub(self.total_tokens_held) as usize;
+            let mut to_add: Vec<u32> = Vec::new();
+            for token_id in &targets {
+                if !self.holdings.contains(*token_id) && !to_add.contains(token_id) {
+                    to_add.push(*token_id);
+                }
+            }
+            to_add.truncate(open_slots);
+
+            let target_weight_bp = if to_add.is_empty() {
+                0
+            } else {
+                let even_weight = 10000_u32.checked_div(targets.len() as u32).unwrap_or(0);
+                let capacity_weight = self
+                    .get_remaining_weight_capacity()
+                    .checked_div(to_add.len() as u32)
+                    .unwrap_or(0);
+                even_weight.min(capacity_weight)
+            };
+
+            for token_id in &to_add {
+                let holding = TokenHolding {
+                    amount: 0,
+                    target_weight_bp,
+                    last_rebalance: timestamp,
+                    fees_collected: 0,
+                };
+
+                self.holdings.insert(*token_id, &holding);
+                self.held_token_ids.push(*token_id);
+                self.total_tokens_held = self.total_tokens_held.saturating_add(1);
+
+                self.env().emit_event(TokenHoldingAdded {
+                    token_id: *token_id,
+                    amount: 0,
+                    target_weight_bp,
+                    added_by: self.env().caller(),
+                    timestamp,
+                });
+            }
+
+            // Flag held tokens that dropped out of the active tier
+            let mut flagged = Vec::new();
+            for token_id in &self.held_token_ids {
+                if !targets.contains(token_id) {
+                    if !self.flagged_for_removal.contains(token_id) {
+                        self.env().emit_event(HoldingFlaggedForRemoval {
+                            token_id: *token_id,
+                            timestamp,
+                        });
+                    }
+                    flagged.push(*token_id);
+                }
+            }
+            let flagged_count = flagged.len() as u32;
+            self.flagged_for_removal = flagged;
+
+            if !to_add.is_empty() {
+                self.trigger_index_update();
+                self.check_auto_rebalance();
+            }
+
+            Ok((to_add.len() as u32, flagged_count))
+        }
+
+        /// Get held tokens flagged for removal by the last active-tier sync
+        #[ink(message)]
+        pub fn get_flagged_for_removal(&self) -> Vec<u32> {
+            self.flagged_for_removal
+                .iter()
+                .copied()
+                .filter(|token_id| self.holdings.contains(*token_id))
+                .collect()
+        }
+
         /// Get portfolio composition with Registry market data
         #[ink(message)]
         pub fn get_portfolio_composition_with_market_data(
@@ -1696,18 +3019,38 @@ mod portfolio {
         // ===== UPDATED PORTFOLIO VALUE CALCULATIONS WITH REGISTRY DATA =====
 
         /// Calculate total portfolio value using real market data from Registry
+        /// In fresh-price mode any stale or unavailable price fails the valuation
+        /// (favors correctness); otherwise amounts are used as a fallback (favors liveness).
         fn calculate_total_portfolio_value(&self) -> Result<u128, Error> {
             if self.total_tokens_held == 0 {
                 return Ok(self.usdc_balance);
             }
 
+            if self.require_fresh_prices {
+                return self.calculate_fresh_portfolio_value();
+            }
+
             let mut total_value = 0u128;
             let mut successful_valuations = 0u32;
 
+            // Fetch all market data in one call, falling back to per-token calls
+            let batch = self
+                .call_registry_get_token_data_batch(self.held_token_ids.clone())
+                .ok();
+
             // Calculate value of each token holding using Registry data
             for token_id in &self.held_token_ids {
                 if let Some(holding) = self.holdings.get(*token_id) {
-                    match self.call_registry_get_token_data(*token_id) {
+                    let token_data = match &batch {
+                        Some(batch) => batch
+                            .iter()
+                            .find(|(id, _)| id == token_id)
+                            .map(|(_, data)| data.clone())
+                            .ok_or(Error::TokenNotFound),
+                        None => self.call_registry_get_token_data(*token_id),
+                    };
+
+                    match token_data {
                         Ok(token_data) => {
                             // Calculate: amount × current_price
                             let token_value =
@@ -1745,6 +3088,83 @@ mod portfolio {
             Ok(total_value)
         }
 
+        /// Calculate total portfolio value, failing on any stale or unavailable price
+        fn calculate_fresh_portfolio_value(&self) -> Result<u128, Error> {
+            let mut total_value = 0u128;
+
+            for token_id in &self.held_token_ids {
+                if let Some(holding) = self.holdings.get(*token_id) {
+                    if self.call_registry_is_price_stale(*token_id)? {
+                        self.emit_operation_failed(
+                            "calculate_fresh_portfolio_value",
+                            &format!("Stale price for token {}", token_id),
+                        );
+                        return Err(Error::PriceStale);
+                    }
+
+                    let token_data = self.call_registry_get_token_data(*token_id)?;
+                    if token_data.price == 0 {
+                        self.emit_operation_failed(
+                            "calculate_fresh_portfolio_value",
+                            &format!("No price for token {}", token_id),
+                        );
+                        return Err(Error::OracleCallFailed);
+                    }
+
+                    let token_value = holding
+                        .amount
+                        .checked_mul(token_data.price)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    total_value = total_value.saturating_add(token_value);
+                }
+            }
+
+            Ok(total_value.saturating_add(self.usdc_balance))
+        }
+
+        /// Get held tokens whose prices are stale or unavailable
+        /// These are the tokens blocking a valuation in fresh-price mode.
+        #[ink(message)]
+        pub fn get_stale_valuation_tokens(&self) -> Result<Vec<u32>, Error> {
+            if self.registry_contract.is_none() {
+                return Err(Error::InvalidParameter);
+            }
+
+            let mut stale_tokens = Vec::new();
+
+            for token_id in &self.held_token_ids {
+                let is_stale = match self.call_registry_is_price_stale(*token_id) {
+                    Ok(false) => !matches!(
+                        self.call_registry_get_token_data(*token_id),
+                        Ok(data) if data.price > 0
+                    ),
+                    _ => true,
+                };
+
+                if is_stale {
+                    stale_tokens.push(*token_id);
+                }
+            }
+
+            Ok(stale_tokens)
+        }
+
+        /// Require fresh prices for valuation (owner only)
+        /// Enabled: valuation fails on stale prices (correctness over liveness).
+        /// Disabled: valuation falls back to token amounts (liveness over correctness).
+        #[ink(message)]
+        pub fn set_require_fresh_prices(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.require_fresh_prices = enabled;
+            Ok(())
+        }
+
+        /// Check if valuation requires fresh prices
+        #[ink(message)]
+        pub fn is_fresh_prices_required(&self) -> bool {
+            self.require_fresh_prices
+        }
+
         /// Calculate portfolio value with fallback mechanisms
         fn calculate_portfolio_value_with_fallback(&self) -> u128 {
             // Try to get real market value first
@@ -1764,6 +3184,8 @@ mod portfolio {
         }
 
         /// Get detailed portfolio valuation breakdown
+        /// Issues one Registry call per holding; gas grows with the number of holdings.
+        /// Prefer `get_portfolio_valuation_breakdown_paged` for large portfolios.
         #[ink(message)]
         pub fn get_portfolio_valuation_breakdown(
             &self,
@@ -1795,6 +3217,31 @@ mod portfolio {
             Ok(breakdown)
         }
 
+        /// Get a page of the portfolio valuation breakdown
+        /// Returns: (Vec<(token_id, amount_held, current_price, total_value)>, next_offset)
+        #[ink(message)]
+        pub fn get_portfolio_valuation_breakdown_paged(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Result<(Vec<(u32, u128, u128, u128)>, Option<u32>), Error> {
+            let (start, end, next_offset) = self.page_bounds(offset, limit)?;
+            let mut breakdown = Vec::new();
+
+            for token_id in &self.held_token_ids[start..end] {
+                if let Some(holding) = self.holdings.get(*token_id) {
+                    let price = match self.call_registry_get_token_data(*token_id) {
+                        Ok(token_data) => token_data.price,
+                        Err(_) => 0, // Include with zero price if Registry call fails
+                    };
+                    let total_value = holding.amount.checked_mul(price).unwrap_or(0);
+                    breakdown.push((*token_id, holding.amount, price, total_value));
+                }
+            }
+
+            Ok((breakdown, next_offset))
+        }
+
         /// Test Registry connection and data availability
         #[ink(message)]
         pub fn test_registry_connection(&self) -> Result<(bool, u32), Error> {
@@ -1814,16 +3261,1950 @@ mod portfolio {
             Ok((successful_calls > 0, successful_calls))
         }
 
-        // ===== ERROR HANDLING HELPER =====
+        // ===== PHASE 5: ON-CHAIN REBALANCING =====
 
-        /// Emit operation failed event for monitoring
-        fn emit_operation_failed(&self, operation: &str, error: &str) {
-            self.env().emit_event(OperationFailed {
-                operation: String::from(operation),
-                error: String::from(error),
-                caller: self.env().caller(),
-                timestamp: self.env().block_timestamp(),
-            });
+        /// Rebalance holdings towards their target weights through the DEX (owner only)
+        /// Only active tier tokens drifting more than `rebalance_threshold_bp` are traded,
+        /// using USDC as the quote asset. Returns the number of swaps performed.
+        /// `force` bypasses the rebalance cooldown for emergencies.
+        #[ink(message)]
+        pub fn execute_rebalance(&mut self, force: bool) -> Result<u32, Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+                self.ensure_rebalancing_allowed()?;
+
+                if !force && self.get_time_until_next_rebalance() > 0 {
+                    self.emit_operation_failed("execute_rebalance", "Rebalance cooldown active");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let dex = self.dex_contract.ok_or_else(|| {
+                    self.emit_operation_failed("execute_rebalance", "DEX contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("execute_rebalance", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let orders = self.compute_rebalance_orders()?;
+                let active_tier_tokens = self.get_rebalancing_targets()?;
+                let timestamp = self.env().block_timestamp();
+                let mut swaps_executed = 0u32;
+
+                for (token_id, amount_delta, estimated_value) in orders {
+                    // Only trade tokens that belong to the active tier
+                    if !active_tier_tokens.contains(&token_id) {
+                        continue;
+                    }
+
+                    let mut holding = match self.holdings.get(token_id) {
+                        Some(holding) => holding,
+                        None => continue,
+                    };
+
+                    let token_data = match self.call_registry_get_token_data(token_id) {
+                        Ok(data) if data.price > 0 => data,
+                        _ => continue, // Cannot price this token safely
+                    };
+
+                    let (direction, amount_in, amount_out) = if amount_delta < 0 {
+                        // Overweight: sell the excess token amount for USDC
+                        let amount_in = amount_delta.unsigned_abs();
+                        let min_amount_out = self.calculate_min_amount_out(estimated_value);
+                        let amount_out = self.call_dex_swap(
+                            dex,
+                            token_data.token_contract,
+                            usdc,
+                            amount_in,
+                            min_amount_out,
+                        )?;
+
+                        holding.amount = holding.amount.saturating_sub(amount_in);
+                        self.usdc_balance = self.usdc_balance.saturating_add(amount_out);
+
+                        (RebalanceDirection::Sell, amount_in, amount_out)
+                    } else {
+                        // Underweight: buy the missing value with USDC above the liquidity buffer
+                        let available_usdc =
+                            self.usdc_balance.saturating_sub(self.min_liquidity_buffer);
+                        let amount_in = estimated_value.min(available_usdc);
+                        if amount_in == 0 {
+                            continue;
+                        }
+
+                        let expected_out = amount_in
+                            .checked_div(token_data.price)
+                            .ok_or(Error::InvalidParameter)?;
+                        let min_amount_out = self.calculate_min_amount_out(expected_out);
+                        let amount_out = self.call_dex_swap(
+                            dex,
+                            usdc,
+                            token_data.token_contract,
+                            amount_in,
+                            min_amount_out,
+                        )?;
+
+                        holding.amount = holding.amount.saturating_add(amount_out);
+                        self.usdc_balance = self.usdc_balance.saturating_sub(amount_in);
+
+                        (RebalanceDirection::Buy, amount_in, amount_out)
+                    };
+
+                    holding.last_rebalance = timestamp;
+                    self.holdings.insert(token_id, &holding);
+                    swaps_executed = swaps_executed.saturating_add(1);
+
+                    self.env().emit_event(RebalanceExecuted {
+                        token_id,
+                        direction,
+                        amount_in,
+                        amount_out,
+                        executed_by: self.env().caller(),
+                        timestamp,
+                    });
+                }
+
+                self.last_rebalance = timestamp;
+
+                // Trigger index update
+                self.trigger_index_update();
+
+                Ok(swaps_executed)
+            })
+        }
+
+        /// Preview the trades a rebalance would perform without mutating state
+        /// Returns: Vec<(token_id, amount_delta, resulting_weight_bp)> where a positive
+        /// delta means buy and a negative delta means sell. Tokens within
+        /// `rebalance_threshold_bp` of their target are omitted.
+        #[ink(message)]
+        pub fn get_rebalance_plan(&self) -> Result<Vec<(u32, i128, u32)>, Error> {
+            let (valuations, total_value) = self.get_market_valuations()?;
+            let mut plan = Vec::new();
+
+            if total_value == 0 {
+                return Ok(plan);
+            }
+
+            for (token_id, holding, price) in valuations {
+                let amount_delta =
+                    match self.calculate_rebalance_delta(&holding, price, total_value)? {
+                        Some(delta) => delta,
+                        None => continue,
+                    };
+
+                let target_amount = i128::try_from(holding.amount)
+                    .map_err(|_| Error::ArithmeticOverflow)?
+                    .checked_add(amount_delta)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let resulting_value = u128::try_from(target_amount)
+                    .map_err(|_| Error::ArithmeticOverflow)?
+                    .checked_mul(price)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let resulting_weight_bp = Self::calculate_weight_bp(resulting_value, total_value);
+
+                plan.push((token_id, amount_delta, resulting_weight_bp));
+            }
+
+            Ok(plan)
+        }
+
+        /// Compute the orders needed to bring holdings back to their target weights
+        /// Returns: Vec<(token_id, amount_delta, estimated_value)> where a positive delta
+        /// means buy and a negative delta means sell. Tokens within
+        /// `rebalance_threshold_bp` of their target are omitted.
+        #[ink(message)]
+        pub fn compute_rebalance_orders(&self) -> Result<Vec<(u32, i128, u128)>, Error> {
+            let (valuations, total_value) = self.get_market_valuations()?;
+            let mut orders = Vec::new();
+
+            if total_value == 0 {
+                return Ok(orders);
+            }
+
+            for (token_id, holding, price) in valuations {
+                if let Some(amount_delta) =
+                    self.calculate_rebalance_delta(&holding, price, total_value)?
+                {
+                    let estimated_value = amount_delta
+                        .unsigned_abs()
+                        .checked_mul(price)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    orders.push((token_id, amount_delta, estimated_value));
+                }
+            }
+
+            Ok(orders)
+        }
+
+        /// Signed token amount needed to reach the target weight, or None if within threshold
+        fn calculate_rebalance_delta(
+            &self,
+            holding: &TokenHolding,
+            price: u128,
+            total_value: u128,
+        ) -> Result<Option<i128>, Error> {
+            let current_value = holding
+                .amount
+                .checked_mul(price)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let current_weight_bp = Self::calculate_weight_bp(current_value, total_value);
+
+            if current_weight_bp.abs_diff(holding.target_weight_bp) <= self.rebalance_threshold_bp {
+                return Ok(None);
+            }
+
+            let target_value = total_value
+                .checked_mul(holding.target_weight_bp as u128)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(Error::InvalidParameter)?;
+            let target_amount = target_value
+                .checked_div(price)
+                .ok_or(Error::InvalidParameter)?;
+
+            let amount_delta = i128::try_from(target_amount)
+                .map_err(|_| Error::ArithmeticOverflow)?
+                .checked_sub(i128::try_from(holding.amount).map_err(|_| Error::ArithmeticOverflow)?)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            Ok(Some(amount_delta))
+        }
+
+        /// Get each holding's live weight and signed drift from its target
+        /// Returns: Vec<(token_id, current_weight_bp, deviation_bp)> where deviation is
+        /// current minus target. Weights are shares of total value including USDC, so
+        /// together with the USDC share they sum to ~10000 (modulo rounding).
+        #[ink(message)]
+        pub fn get_weight_deviations(&self) -> Result<Vec<(u32, u32, i32)>, Error> {
+            let (valuations, total_value) = self.get_market_valuations()?;
+
+            if total_value == 0 {
+                return Err(Error::OracleCallFailed);
+            }
+
+            Self::calculate_weight_deviations(&valuations, total_value)
+        }
+
+        /// Current weight and deviation from target for already priced holdings
+        fn calculate_weight_deviations(
+            valuations: &[(u32, TokenHolding, u128)],
+            total_value: u128,
+        ) -> Result<Vec<(u32, u32, i32)>, Error> {
+            let mut deviations = Vec::new();
+            for (token_id, holding, price) in valuations {
+                let current_value = holding
+                    .amount
+                    .checked_mul(*price)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let current_weight_bp = Self::calculate_weight_bp(current_value, total_value);
+                let deviation_bp =
+                    (current_weight_bp as i32).saturating_sub(holding.target_weight_bp as i32);
+
+                deviations.push((*token_id, current_weight_bp, deviation_bp));
+            }
+
+            Ok(deviations)
+        }
+
+        /// Get held tokens whose absolute drift exceeds `rebalance_threshold_bp`
+        #[ink(message)]
+        pub fn get_tokens_exceeding_threshold(&self) -> Result<Vec<u32>, Error> {
+            Ok(self
+                .get_weight_deviations()?
+                .into_iter()
+                .filter(|(_, _, deviation_bp)| {
+                    deviation_bp.unsigned_abs() > self.rebalance_threshold_bp
+                })
+                .map(|(token_id, _, _)| token_id)
+                .collect())
+        }
+
+        /// Get value, performance, drift, liquidity and price freshness in one report
+        /// Prices every holding once. Holdings with stale, missing or zero prices are
+        /// counted in `stale_prices` and left out of the value and drift figures.
+        #[ink(message)]
+        pub fn get_portfolio_health(&self) -> Result<PortfolioHealth, Error> {
+            let observations = self
+                .held_token_ids
+                .iter()
+                .filter_map(|token_id| {
+                    let holding = self.holdings.get(*token_id)?;
+                    let price = self
+                        .call_registry_get_token_data(*token_id)
+                        .ok()
+                        .map(|data| data.price);
+                    let is_stale =
+                        !matches!(self.call_registry_is_price_stale(*token_id), Ok(false));
+                    Some((*token_id, holding, price, is_stale))
+                })
+                .collect();
+            let (valuations, total_value, stale_prices) =
+                Self::value_observed_holdings(self.usdc_balance, observations)?;
+
+            let index_value = if !self.index_tracking_enabled || self.base_portfolio_value == 0 {
+                self.index_base_value
+            } else {
+                self.index_value_from_portfolio_value(total_value)?
+            };
+            let performance_bp = self.calculate_performance_bp(index_value)?;
+
+            let max_drift_bp = if total_value == 0 {
+                0
+            } else {
+                Self::calculate_weight_deviations(&valuations, total_value)?
+                    .into_iter()
+                    .map(|(_, _, deviation_bp)| deviation_bp.unsigned_abs())
+                    .max()
+                    .unwrap_or(0)
+            };
+
+            Ok(PortfolioHealth {
+                total_value,
+                index_value,
+                performance_bp,
+                max_drift_bp,
+                needs_rebalance: max_drift_bp > self.rebalance_threshold_bp,
+                liquidity_ok: self.usdc_balance >= self.min_liquidity_buffer,
+                stale_prices,
+            })
+        }
+
+        /// Check whether any holding's market weight drifts past `rebalance_threshold_bp`
+        #[ink(message)]
+        pub fn should_rebalance(&self) -> Result<bool, Error> {
+            Ok(!self.get_tokens_exceeding_threshold()?.is_empty())
+        }
+
+        /// Enable/disable `RebalanceNeeded` signals after holding changes (owner only)
+        #[ink(message)]
+        pub fn set_auto_rebalance(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.auto_rebalance_enabled = enabled;
+            Ok(())
+        }
+
+        /// Check if auto-rebalance signalling is enabled
+        #[ink(message)]
+        pub fn is_auto_rebalance_enabled(&self) -> bool {
+            self.auto_rebalance_enabled
+        }
+
+        /// Internal method to signal off-chain keepers that `execute_rebalance` is due
+        /// Missing market data is not a signal; valuation failures are reported elsewhere.
+        fn check_auto_rebalance(&self) {
+            if self.auto_rebalance_enabled && self.should_rebalance().unwrap_or(false) {
+                self.env().emit_event(RebalanceNeeded {
+                    threshold_bp: self.rebalance_threshold_bp,
+                    timestamp: self.env().block_timestamp(),
+                });
+            }
+        }
+
+        /// Get last rebalancing timestamp
+        #[ink(message)]
+        pub fn get_last_rebalance(&self) -> u64 {
+            self.last_rebalance
+        }
+
+        /// Get milliseconds until the rebalance cooldown expires (0 = ready)
+        #[ink(message)]
+        pub fn get_time_until_next_rebalance(&self) -> u64 {
+            let elapsed = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(self.last_rebalance);
+            self.min_rebalance_interval_ms.saturating_sub(elapsed)
+        }
+
+        /// Set minimum time between rebalances in milliseconds (owner only)
+        #[ink(message)]
+        pub fn set_min_rebalance_interval(&mut self, interval_ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.min_rebalance_interval_ms = interval_ms;
+            Ok(())
+        }
+
+        /// Get minimum time between rebalances in milliseconds
+        #[ink(message)]
+        pub fn get_min_rebalance_interval(&self) -> u64 {
+            self.min_rebalance_interval_ms
+        }
+
+        /// Price every holding via the Registry, failing if any market data is missing
+        /// Returns: (Vec<(token_id, holding, price)>, total_value including USDC)
+        fn get_market_valuations(&self) -> Result<(Vec<(u32, TokenHolding, u128)>, u128), Error> {
+            let mut valuations = Vec::new();
+            let mut total_value = self.usdc_balance;
+
+            for token_id in &self.held_token_ids {
+                if let Some(holding) = self.holdings.get(*token_id) {
+                    let token_data = self
+                        .call_registry_get_token_data(*token_id)
+                        .map_err(|_| Error::OracleCallFailed)?;
+
+                    if token_data.price == 0 {
+                        return Err(Error::OracleCallFailed);
+                    }
+
+                    let token_value = holding
+                        .amount
+                        .checked_mul(token_data.price)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    total_value = total_value.saturating_add(token_value);
+                    valuations.push((*token_id, holding, token_data.price));
+                }
+            }
+
+            Ok((valuations, total_value))
+        }
+
+        /// Value holdings observed as (token_id, holding, price, is_stale)
+        /// Stale, missing or zero prices are counted instead of valued.
+        /// Returns: (Vec<(token_id, holding, price)>, total_value including USDC, stale count)
+        fn value_observed_holdings(
+            usdc_balance: u128,
+            observations: Vec<(u32, TokenHolding, Option<u128>, bool)>,
+        ) -> Result<(Vec<(u32, TokenHolding, u128)>, u128, u32), Error> {
+            let mut valuations = Vec::new();
+            let mut total_value = usdc_balance;
+            let mut stale_prices = 0u32;
+
+            for (token_id, holding, price, is_stale) in observations {
+                let price = match price {
+                    Some(price) if price > 0 && !is_stale => price,
+                    _ => {
+                        stale_prices = stale_prices.saturating_add(1);
+                        continue;
+                    }
+                };
+
+                let token_value = holding
+                    .amount
+                    .checked_mul(price)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                total_value = total_value.saturating_add(token_value);
+                valuations.push((token_id, holding, price));
+            }
+
+            Ok((valuations, total_value, stale_prices))
+        }
+
+        /// Typed reference to the Registry through `shared::Registry`
+        fn registry_ref(registry: AccountId) -> ink::contract_ref!(shared::Registry) {
+            registry.into()
+        }
+
+        /// Typed reference to the Oracle through `shared::Oracle`
+        fn oracle_ref(oracle: AccountId) -> ink::contract_ref!(shared::Oracle) {
+            oracle.into()
+        }
+
+        /// Cross-contract call to swap tokens through the DEX
+        /// The DEX reverts with SlippageExceeded if the output is below `min_amount_out`,
+        /// or PriceImpactTooHigh if the leg would move the pool more than `max_slippage_bp`.
+        /// The DEX measures impact after its swap fee, so the fee is added to the cap.
+        fn call_dex_swap(
+            &self,
+            dex: AccountId,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+            min_amount_out: u128,
+        ) -> Result<u128, Error> {
+            let fee_bp = self.call_dex_swap_fee(dex)?;
+            let max_impact_bp = Self::price_impact_cap_bp(self.max_slippage_bp, fee_bp);
+
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(dex)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new([
+                        0x0D, 0x0E, 0x0F, 0x12, // swap_with_limits selector
+                    ]))
+                    .push_arg(from)
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(vec![from, to])
+                    .push_arg(min_amount_out)
+                    .push_arg(Some(max_impact_bp)),
+                )
+                .returns::<Result<u128, Error>>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(Ok(amount_out))) => Ok(amount_out),
+                Ok(Ok(Err(Error::SlippageExceeded))) => {
+                    self.emit_operation_failed(
+                        "execute_rebalance",
+                        "Swap exceeded maximum slippage",
+                    );
+                    Err(Error::SlippageExceeded)
+                }
+                Ok(Ok(Err(Error::PriceImpactTooHigh))) => {
+                    self.emit_operation_failed(
+                        "execute_rebalance",
+                        "Swap price impact exceeded maximum slippage",
+                    );
+                    Err(Error::PriceImpactTooHigh)
+                }
+                Ok(Ok(Err(error))) => {
+                    self.emit_operation_failed("call_dex_swap", "DEX returned error");
+                    Err(error)
+                }
+                _ => {
+                    self.emit_operation_failed("call_dex_swap", "DEX call failed");
+                    Err(Error::OracleCallFailed)
+                }
+            }
+        }
+
+        /// Cross-contract call to read the DEX swap fee in basis points
+        fn call_dex_swap_fee(&self, dex: AccountId) -> Result<u32, Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(dex)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(ink::env::call::ExecutionInput::new(
+                    ink::env::call::Selector::new(ink::selector_bytes!("get_swap_fee")),
+                ))
+                .returns::<u32>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(fee_bp)) => Ok(fee_bp),
+                _ => {
+                    self.emit_operation_failed("call_dex_swap_fee", "DEX call failed");
+                    Err(Error::OracleCallFailed)
+                }
+            }
+        }
+
+        /// Price impact cap for a swap: slippage tolerance plus the DEX fee (max 100%)
+        fn price_impact_cap_bp(max_slippage_bp: u32, fee_bp: u32) -> u32 {
+            max_slippage_bp.saturating_add(fee_bp).min(10000)
+        }
+
+        /// Minimum acceptable swap output given `max_slippage_bp`
+        fn calculate_min_amount_out(&self, expected_out: u128) -> u128 {
+            let tolerance_bp = 10000u128.saturating_sub(self.max_slippage_bp as u128);
+            expected_out
+                .saturating_mul(tolerance_bp)
+                .checked_div(10000)
+                .unwrap_or(0)
+        }
+
+        /// Calculate a value's share of the total in basis points (capped at 100%)
+        fn calculate_weight_bp(value: u128, total_value: u128) -> u32 {
+            value
+                .saturating_mul(10000)
+                .checked_div(total_value)
+                .unwrap_or(0)
+                .min(10000) as u32
+        }
+
+        // ===== PHASE 6: MINT & REDEEM =====
+
+        /// Deposit USDC and mint W3PI index tokens at the current index value
+        /// The buy fee is deducted from the deposit and credited to collected fees.
+        /// Returns the amount of index tokens minted.
+        #[ink(message)]
+        pub fn deposit_and_mint(&mut self, usdc_amount: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_deposits_allowed()?;
+
+                if usdc_amount == 0 {
+                    self.emit_operation_failed("deposit_and_mint", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("deposit_and_mint", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("deposit_and_mint", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let fee_amount = Self::calculate_fee(usdc_amount, self.fee_config.buy_fee_bp)?;
+                let net_amount = usdc_amount
+                    .checked_sub(fee_amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                // Portfolio must reach the minimum size before accepting trades
+                let portfolio_value = self
+                    .calculate_portfolio_value_with_fallback()
+                    .saturating_add(net_amount);
+                if portfolio_value < self.min_portfolio_value {
+                    self.emit_operation_failed(
+                        "deposit_and_mint",
+                        "Portfolio value below minimum after deposit",
+                    );
+                    return Err(Error::InvalidParameter);
+                }
+
+                let index_value = self.current_index_value;
+                let tokens_minted = net_amount
+                    .checked_mul(INDEX_TOKEN_UNIT)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    .checked_div(index_value)
+                    .ok_or(Error::InvalidParameter)?;
+
+                if tokens_minted == 0 {
+                    self.emit_operation_failed("deposit_and_mint", "Deposit too small to mint");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                // Update accounting before external calls
+                self.usdc_balance = self.usdc_balance.saturating_add(net_amount);
+                self.total_fees_collected = self.total_fees_collected.saturating_add(fee_amount);
+
+                self.call_token_transfer_from(usdc, caller, contract, usdc_amount)?;
+                self.call_token_mint(token, tokens_minted)?;
+                self.call_token_transfer(token, caller, tokens_minted)?;
+
+                self.env().emit_event(IndexTokensMinted {
+                    user: caller,
+                    usdc_amount,
+                    fee_amount,
+                    tokens_minted,
+                    index_value,
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(tokens_minted)
+            })
+        }
+
+        /// Redeem W3PI index tokens for USDC at the current index value
+        /// The sell fee is deducted from the proceeds and credited to collected fees.
+        /// Returns the amount of USDC paid out.
+        #[ink(message)]
+        pub fn redeem(&mut self, token_amount: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_not_emergency_paused()?;
+
+                if token_amount == 0 {
+                    self.emit_operation_failed("redeem", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("redeem", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("redeem", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let index_value = self.current_index_value;
+                let gross_amount = token_amount
+                    .checked_mul(index_value)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    .checked_div(INDEX_TOKEN_UNIT)
+                    .ok_or(Error::InvalidParameter)?;
+
+                let fee_amount = Self::calculate_fee(gross_amount, self.fee_config.sell_fee_bp)?;
+                let usdc_returned = gross_amount
+                    .checked_sub(fee_amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                if usdc_returned == 0 {
+                    self.emit_operation_failed("redeem", "Redemption too small");
+                    return Err(Error::InvalidParameter);
+                }
+
+                // Redemptions must never drain the liquidity buffer
+                if self.usdc_balance < gross_amount.saturating_add(self.min_liquidity_buffer) {
+                    self.emit_operation_failed("redeem", "Insufficient liquidity buffer");
+                    return Err(Error::InsufficientBalance);
+                }
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                // Update accounting before external calls
+                self.usdc_balance = self
+                    .usdc_balance
+                    .checked_sub(gross_amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.total_fees_collected = self.total_fees_collected.saturating_add(fee_amount);
+
+                self.call_token_transfer_from(token, caller, contract, token_amount)?;
+                self.call_token_burn(token, token_amount)?;
+                self.call_token_transfer(usdc, caller, usdc_returned)?;
+
+                self.env().emit_event(IndexTokensRedeemed {
+                    user: caller,
+                    tokens_burned: token_amount,
+                    fee_amount,
+                    usdc_returned,
+                    index_value,
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(usdc_returned)
+            })
+        }
+
+        /// Deposit an index-eligible token in kind and mint W3PI shares at the live NAV per share
+        /// The index base value prices shares while no W3PI is in circulation.
+        /// The buy fee is kept in kind and tracked on the holding. Returns the shares minted.
+        #[ink(message)]
+        pub fn deposit_for_shares(&mut self, token_id: u32, amount: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_deposits_allowed()?;
+
+                if amount == 0 {
+                    self.emit_operation_failed("deposit_for_shares", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("deposit_for_shares", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let mut holding = self.holdings.get(token_id).ok_or_else(|| {
+                    self.emit_operation_failed("deposit_for_shares", "Token not held by portfolio");
+                    Error::TokenNotFound
+                })?;
+
+                if !self.get_rebalancing_targets()?.contains(&token_id) {
+                    self.emit_operation_failed("deposit_for_shares", "Token not in active tier");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let token_data = self.call_registry_get_token_data(token_id)?;
+                if token_data.price == 0 {
+                    self.emit_operation_failed("deposit_for_shares", "Token price unavailable");
+                    return Err(Error::OracleCallFailed);
+                }
+
+                let fee_amount = Self::calculate_fee(amount, self.fee_config.buy_fee_bp)?;
+                let net_amount = amount
+                    .checked_sub(fee_amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                let deposit_value = net_amount
+                    .checked_mul(token_data.price)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                // Price shares off the live value so existing holders are not diluted
+                let total_supply = self.call_token_total_supply(token)?;
+                let nav_per_share = if total_supply == 0 {
+                    self.index_base_value
+                } else {
+                    let portfolio_value = self
+                        .calculate_total_portfolio_value()
+                        .map_err(|_| Error::OracleCallFailed)?;
+                    Self::nav_per_share(portfolio_value, total_supply)?
+                };
+                if nav_per_share == 0 {
+                    self.emit_operation_failed("deposit_for_shares", "Portfolio has no value");
+                    return Err(Error::InvalidParameter);
+                }
+                let shares_minted = Self::shares_for_value(deposit_value, nav_per_share)?;
+
+                if shares_minted == 0 {
+                    self.emit_operation_failed("deposit_for_shares", "Deposit too small to mint");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                // Update accounting before external calls
+                holding.amount = holding.amount.saturating_add(amount);
+                self.holdings.insert(token_id, &holding);
+                self.record_token_fee(token_id, fee_amount);
+
+                self.call_token_transfer_from(token_data.token_contract, caller, contract, amount)?;
+                self.call_token_mint(token, shares_minted)?;
+                self.call_token_transfer(token, caller, shares_minted)?;
+
+                self.env().emit_event(SharesMinted {
+                    user: caller,
+                    token_id,
+                    amount_deposited: amount,
+                    fee_amount,
+                    deposit_value,
+                    shares_minted,
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(shares_minted)
+            })
+        }
+
+        /// Burn W3PI shares for a pro-rata share of USDC and each held token
+        /// The sell fee is kept in kind and tracked on each holding.
+        /// Returns the value paid out: USDC plus each token amount at its registry price.
+        #[ink(message)]
+        pub fn redeem_shares(&mut self, shares: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_not_emergency_paused()?;
+
+                if shares == 0 {
+                    self.emit_operation_failed("redeem_shares", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                if self.held_token_ids.is_empty() {
+                    self.emit_operation_failed("redeem_shares", "Portfolio has no holdings");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("redeem_shares", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let total_supply = self.call_token_total_supply(token)?;
+                if total_supply == 0 || shares > total_supply {
+                    self.emit_operation_failed(
+                        "redeem_shares",
+                        "Amount exceeds index token supply",
+                    );
+                    return Err(Error::InsufficientBalance);
+                }
+
+                // The USDC leg pays out the same pro-rata share as each holding
+                let usdc_gross = Self::pro_rata_share(self.usdc_balance, shares, total_supply)?;
+                let usdc_fee = Self::calculate_fee(usdc_gross, self.fee_config.sell_fee_bp)?;
+                let usdc_returned = usdc_gross
+                    .checked_sub(usdc_fee)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let usdc = if usdc_returned > 0 {
+                    Some(self.usdc_contract.ok_or_else(|| {
+                        self.emit_operation_failed("redeem_shares", "USDC contract not set");
+                        Error::InvalidParameter
+                    })?)
+                } else {
+                    None
+                };
+
+                let mut fee_value = usdc_fee;
+                let mut value_returned = usdc_returned;
+
+                // Split each pro-rata payout into the amount sent and the fee retained
+                let mut payouts = Vec::new();
+                for (token_id, token_contract, gross_amount, price) in
+                    self.calculate_priced_holding_payouts(shares, total_supply)?
+                {
+                    let fee_amount =
+                        Self::calculate_fee(gross_amount, self.fee_config.sell_fee_bp)?;
+                    let net_amount = gross_amount
+                        .checked_sub(fee_amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+
+                    self.record_token_fee(token_id, fee_amount);
+                    fee_value = Self::add_priced_amount(fee_value, fee_amount, price)?;
+                    value_returned = Self::add_priced_amount(value_returned, net_amount, price)?;
+
+                    if net_amount > 0 {
+                        payouts.push((token_id, token_contract, net_amount));
+                    }
+                }
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                // Update accounting before external calls
+                self.usdc_balance = self
+                    .usdc_balance
+                    .checked_sub(usdc_gross)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.total_fees_collected = self.total_fees_collected.saturating_add(usdc_fee);
+                self.apply_holding_payouts(&payouts);
+
+                self.call_token_transfer_from(token, caller, contract, shares)?;
+                self.call_token_burn(token, shares)?;
+
+                if let Some(usdc) = usdc {
+                    self.call_token_transfer(usdc, caller, usdc_returned)?;
+                }
+                for (_, token_contract, amount) in payouts.iter() {
+                    self.call_token_transfer(*token_contract, caller, *amount)?;
+                }
+
+                self.env().emit_event(SharesBurned {
+                    user: caller,
+                    shares_burned: shares,
+                    fee_value,
+                    value_returned,
+                    usdc_returned,
+                    tokens_returned: payouts
+                        .iter()
+                        .map(|(token_id, _, amount)| (*token_id, *amount))
+                        .collect(),
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(value_returned)
+            })
+        }
+
+        /// Attribute a fee charged in kind to a token's holding and running total
+        fn record_token_fee(&mut self, token_id: u32, fee_amount: u128) {
+            if fee_amount == 0 {
+                return;
+            }
+
+            if let Some(mut holding) = self.holdings.get(token_id) {
+                holding.fees_collected = holding.fees_collected.saturating_add(fee_amount);
+                self.holdings.insert(token_id, &holding);
+            }
+
+            let total = self.collected_fees.get(token_id).unwrap_or(0);
+            self.collected_fees
+                .insert(token_id, &total.saturating_add(fee_amount));
+        }
+
+        /// Get total fees collected in kind for a token
+        #[ink(message)]
+        pub fn get_token_fees_collected(&self, token_id: u32) -> u128 {
+            self.collected_fees.get(token_id).unwrap_or(0)
+        }
+
+        /// Get fees collected in kind for every held token
+        #[ink(message)]
+        pub fn get_all_token_fees(&self) -> Vec<(u32, u128)> {
+            self.held_token_ids
+                .iter()
+                .map(|token_id| (*token_id, self.get_token_fees_collected(*token_id)))
+                .collect()
+        }
+
+        /// Get current USDC liquidity held by the portfolio
+        #[ink(message)]
+        pub fn get_usdc_balance(&self) -> u128 {
+            self.usdc_balance
+        }
+
+        /// Redeem index tokens for a pro-rata share of USDC and holdings (Emergency state only)
+        /// No sell fee is charged. This is the only user exit available while paused.
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self, token_amount: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                if self.state != PortfolioState::Emergency {
+                    self.emit_operation_failed(
+                        "emergency_withdraw",
+                        "Portfolio is not in emergency state",
+                    );
+                    return Err(Error::InvalidParameter);
+                }
+
+                if token_amount == 0 {
+                    self.emit_operation_failed("emergency_withdraw", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("emergency_withdraw", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("emergency_withdraw", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let total_supply = self.call_token_total_supply(token)?;
+                if total_supply == 0 || token_amount > total_supply {
+                    self.emit_operation_failed(
+                        "emergency_withdraw",
+                        "Amount exceeds index token supply",
+                    );
+                    return Err(Error::InsufficientBalance);
+                }
+
+                let usdc_returned =
+                    Self::pro_rata_share(self.usdc_balance, token_amount, total_supply)?;
+
+                // Work out each holding's share before touching any state
+                let payouts = self.calculate_holding_payouts(token_amount, total_supply)?;
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                self.usdc_balance = self
+                    .usdc_balance
+                    .checked_sub(usdc_returned)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.apply_holding_payouts(&payouts);
+
+                self.call_token_transfer_from(token, caller, contract, token_amount)?;
+                self.call_token_burn(token, token_amount)?;
+
+                if usdc_returned > 0 {
+                    self.call_token_transfer(usdc, caller, usdc_returned)?;
+                }
+                for (_, token_contract, share) in payouts.iter() {
+                    self.call_token_transfer(*token_contract, caller, *share)?;
+                }
+
+                self.env().emit_event(EmergencyWithdrawal {
+                    user: caller,
+                    tokens_burned: token_amount,
+                    usdc_returned,
+                    holdings_returned: payouts
+                        .iter()
+                        .map(|(token_id, _, share)| (*token_id, *share))
+                        .collect(),
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(usdc_returned)
+            })
+        }
+
+        /// Burn index tokens for a pro-rata share of each held token (Emergency state only)
+        /// Returns the (token_id, amount_sent) pairs transferred to the caller.
+        #[ink(message)]
+        pub fn emergency_redeem(&mut self, shares: u128) -> Result<Vec<(u32, u128)>, Error> {
+            non_reentrant!(self, {
+                if self.state != PortfolioState::Emergency {
+                    self.emit_operation_failed(
+                        "emergency_redeem",
+                        "Portfolio is not in emergency state",
+                    );
+                    return Err(Error::InvalidParameter);
+                }
+
+                if shares == 0 {
+                    self.emit_operation_failed("emergency_redeem", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                if self.held_token_ids.is_empty() {
+                    self.emit_operation_failed("emergency_redeem", "Portfolio has no holdings");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let token = self.token_contract.ok_or_else(|| {
+                    self.emit_operation_failed("emergency_redeem", "Token contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let total_supply = self.call_token_total_supply(token)?;
+                if total_supply == 0 || shares > total_supply {
+                    self.emit_operation_failed(
+                        "emergency_redeem",
+                        "Amount exceeds index token supply",
+                    );
+                    return Err(Error::InsufficientBalance);
+                }
+
+                let payouts = self.calculate_holding_payouts(shares, total_supply)?;
+
+                let caller = self.env().caller();
+                let contract = self.env().account_id();
+
+                self.apply_holding_payouts(&payouts);
+
+                self.call_token_transfer_from(token, caller, contract, shares)?;
+                self.call_token_burn(token, shares)?;
+
+                for (_, token_contract, amount) in payouts.iter() {
+                    self.call_token_transfer(*token_contract, caller, *amount)?;
+                }
+
+                let tokens_returned: Vec<(u32, u128)> = payouts
+                    .iter()
+                    .map(|(token_id, _, amount)| (*token_id, *amount))
+                    .collect();
+
+                self.env().emit_event(EmergencyRedemption {
+                    user: caller,
+                    shares_burned: shares,
+                    tokens_returned: tokens_returned.clone(),
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(tokens_returned)
+            })
+        }
+
+        /// Calculate each holding's pro-rata payout as (token_id, token_contract, amount)
+        fn calculate_holding_payouts(
+            &self,
+            shares: u128,
+            total_supply: u128,
+        ) -> Result<Vec<(u32, AccountId, u128)>, Error> {
+            Ok(self
+                .calculate_priced_holding_payouts(shares, total_supply)?
+                .into_iter()
+                .map(|(token_id, token_contract, amount, _)| (token_id, token_contract, amount))
+                .collect())
+        }
+
+        /// Calculate each holding's pro-rata payout as (token_id, token_contract, amount, price)
+        fn calculate_priced_holding_payouts(
+            &self,
+            shares: u128,
+            total_supply: u128,
+        ) -> Result<Vec<(u32, AccountId, u128, u128)>, Error> {
+            let mut payouts = Vec::new();
+            for token_id in self.held_token_ids.iter() {
+                if let Some(holding) = self.holdings.get(*token_id) {
+                    let amount = Self::pro_rata_share(holding.amount, shares, total_supply)?;
+                    if amount == 0 {
+                        continue;
+                    }
+                    let token_data = self.call_registry_get_token_data(*token_id)?;
+                    payouts.push((
+                        *token_id,
+                        token_data.token_contract,
+                        amount,
+                        token_data.price,
+                    ));
+                }
+            }
+            Ok(payouts)
+        }
+
+        /// Calculate `total + amount * price` with overflow checks
+        fn add_priced_amount(total: u128, amount: u128, price: u128) -> Result<u128, Error> {
+            amount
+                .checked_mul(price)
+                .and_then(|value| total.checked_add(value))
+                .ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Decrement holdings by the amounts paid out
+        fn apply_holding_payouts(&mut self, payouts: &[(u32, AccountId, u128)]) {
+            for (token_id, _, amount) in payouts.iter() {
+                if let Some(mut holding) = self.holdings.get(*token_id) {
+                    holding.amount = holding.amount.saturating_sub(*amount);
+                    self.holdings.insert(*token_id, &holding);
+                }
+            }
+        }
+
+        /// Calculate `amount * part / whole` with overflow checks
+        fn pro_rata_share(amount: u128, part: u128, whole: u128) -> Result<u128, Error> {
+            amount
+                .checked_mul(part)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(whole)
+                .ok_or(Error::InvalidParameter)
+        }
+
+        /// Deposit USDC into the portfolio's liquidity buffer
+        #[ink(message)]
+        pub fn deposit_usdc(&mut self, amount: u128) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_deposits_allowed()?;
+
+                if amount == 0 {
+                    self.emit_operation_failed("deposit_usdc", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("deposit_usdc", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let caller = self.env().caller();
+                let old_balance = self.usdc_balance;
+                let new_balance = old_balance.checked_add(amount).ok_or_else(|| {
+                    self.emit_operation_failed("deposit_usdc", "USDC balance overflow");
+                    Error::InvalidParameter
+                })?;
+
+                self.usdc_balance = new_balance;
+
+                self.call_token_transfer_from(usdc, caller, self.env().account_id(), amount)?;
+
+                self.env().emit_event(LiquidityBufferChanged {
+                    old_balance,
+                    new_balance,
+                    changed_by: caller,
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(())
+            })
+        }
+
+        /// Withdraw USDC from the liquidity buffer (owner only)
+        /// The remaining balance must stay at or above `min_liquidity_buffer`.
+        #[ink(message)]
+        pub fn withdraw_usdc(&mut self, amount: u128) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+
+                if amount == 0 {
+                    self.emit_operation_failed("withdraw_usdc", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("withdraw_usdc", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let old_balance = self.usdc_balance;
+                let new_balance = old_balance
+                    .checked_sub(amount)
+                    .filter(|remaining| *remaining >= self.min_liquidity_buffer)
+                    .ok_or_else(|| {
+                        self.emit_operation_failed(
+                            "withdraw_usdc",
+                            "Withdrawal would drop below minimum liquidity buffer",
+                        );
+                        Error::InsufficientBalance
+                    })?;
+
+                self.usdc_balance = new_balance;
+
+                let caller = self.env().caller();
+                self.call_token_transfer(usdc, caller, amount)?;
+
+                self.env().emit_event(LiquidityBufferChanged {
+                    old_balance,
+                    new_balance,
+                    changed_by: caller,
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(())
+            })
+        }
+
+        /// Recover tokens sent to the portfolio by mistake (owner only)
+        /// USDC and tokens backing a tracked holding cannot be swept. If the Registry
+        /// cannot confirm a held token's contract, the sweep is refused.
+        #[ink(message)]
+        pub fn sweep_token(
+            &mut self,
+            token_contract: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+
+                if amount == 0 {
+                    self.emit_operation_failed("sweep_token", "Amount cannot be zero");
+                    return Err(Error::AmountZero);
+                }
+
+                if to == AccountId::from([0u8; 32]) {
+                    return Err(Error::ZeroAddress);
+                }
+
+                if self.usdc_contract == Some(token_contract) {
+                    self.emit_operation_failed(
+                        "sweep_token",
+                        "USDC is tracked in the liquidity buffer",
+                    );
+                    return Err(Error::InvalidParameter);
+                }
+
+                for token_id in &self.held_token_ids {
+                    let held_contract =
+                        self.call_registry_get_token_data(*token_id)?.token_contract;
+                    if held_contract == token_contract {
+                        self.emit_operation_failed(
+                            "sweep_token",
+                            &format!("Token backs tracked holding {}", token_id),
+                        );
+                        return Err(Error::InvalidParameter);
+                    }
+                }
+
+                self.call_token_transfer(token_contract, to, amount)?;
+
+                self.env().emit_event(TokenSwept {
+                    token_contract,
+                    to,
+                    amount,
+                    swept_by: self.env().caller(),
+                    timestamp: self.env().block_timestamp(),
+                });
+
+                Ok(())
+            })
+        }
+
+        /// Calculate a fee in basis points of an amount
+        fn calculate_fee(amount: u128, fee_bp: u32) -> Result<u128, Error> {
+            amount
+                .checked_mul(fee_bp as u128)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(Error::InvalidParameter)
+        }
+
+        // ===== FEE DISTRIBUTION =====
+
+        /// Register or update a fee beneficiary and its share in basis points (owner only)
+        #[ink(message)]
+        pub fn set_fee_beneficiary(
+            &mut self,
+            beneficiary: AccountId,
+            share_bp: u32,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if beneficiary == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            if share_bp == 0 {
+                self.emit_operation_failed(
+                    "set_fee_beneficiary",
+                    "Share cannot be zero, use remove_fee_beneficiary",
+                );
+                return Err(Error::InvalidParameter);
+            }
+
+            let old_share_bp = self.fee_beneficiaries.get(beneficiary).unwrap_or(0);
+            let new_total = self
+                .total_beneficiary_share_bp
+                .saturating_sub(old_share_bp)
+                .saturating_add(share_bp);
+
+            if new_total > 10000 {
+                self.emit_operation_failed(
+                    "set_fee_beneficiary",
+                    "Total beneficiary shares would exceed 100%",
+                );
+                return Err(Error::InvalidParameter);
+            }
+
+            if old_share_bp == 0 {
+                self.fee_beneficiary_list.push(beneficiary);
+            }
+
+            self.fee_beneficiaries.insert(beneficiary, &share_bp);
+            self.total_beneficiary_share_bp = new_total;
+
+            self.env().emit_event(FeeBeneficiaryUpdated {
+                beneficiary,
+                old_share_bp,
+                new_share_bp: share_bp,
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(())
+        }
+
+        /// Remove a fee beneficiary (owner only)
+        #[ink(message)]
+        pub fn remove_fee_beneficiary(&mut self, beneficiary: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let old_share_bp = self.fee_beneficiaries.get(beneficiary).ok_or_else(|| {
+                self.emit_operation_failed("remove_fee_beneficiary", "Beneficiary not found");
+                Error::InvalidParameter
+            })?;
+
+            self.fee_beneficiaries.remove(beneficiary);
+            if let Some(pos) = self
+                .fee_beneficiary_list
+                .iter()
+                .position(|&x| x == beneficiary)
+            {
+                self.fee_beneficiary_list.remove(pos);
+            }
+            self.total_beneficiary_share_bp =
+                self.total_beneficiary_share_bp.saturating_sub(old_share_bp);
+
+            self.env().emit_event(FeeBeneficiaryUpdated {
+                beneficiary,
+                old_share_bp,
+                new_share_bp: 0,
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(())
+        }
+
+        /// Split collected fees across beneficiaries by share and pay them in USDC (owner only)
+        /// Returns the total amount distributed.
+        #[ink(message)]
+        pub fn distribute_fees(&mut self) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+
+                if self.total_fees_collected == 0 || self.fee_beneficiary_list.is_empty() {
+                    self.emit_operation_failed("distribute_fees", "Nothing to distribute");
+                    return Err(Error::InvalidParameter);
+                }
+
+                let usdc = self.usdc_contract.ok_or_else(|| {
+                    self.emit_operation_failed("distribute_fees", "USDC contract not set");
+                    Error::InvalidParameter
+                })?;
+
+                let fees_to_split = self.total_fees_collected;
+                let timestamp = self.env().block_timestamp();
+                let mut total_distributed = 0u128;
+
+                for beneficiary in self.fee_beneficiary_list.clone() {
+                    let share_bp = self.fee_beneficiaries.get(beneficiary).unwrap_or(0);
+                    let amount = Self::calculate_fee(fees_to_split, share_bp)?;
+                    if amount == 0 {
+                        continue;
+                    }
+
+                    self.call_token_transfer(usdc, beneficiary, amount)?;
+                    total_distributed = total_distributed.saturating_add(amount);
+
+                    self.env().emit_event(FeesDistributed {
+                        beneficiary,
+                        amount,
+                        share_bp,
+                        timestamp,
+                    });
+                }
+
+                // Rounding dust from the allocated portion goes to the owner;
+                // unallocated shares stay collected for the next round
+                let allocated =
+                    Self::calculate_fee(fees_to_split, self.total_beneficiary_share_bp)?;
+                let dust = allocated.saturating_sub(total_distributed);
+                if dust > 0 {
+                    let owner = self.owner;
+                    self.call_token_transfer(usdc, owner, dust)?;
+                    total_distributed = total_distributed.saturating_add(dust);
+
+                    self.env().emit_event(FeesDistributed {
+                        beneficiary: owner,
+                        amount: dust,
+                        share_bp: 0,
+                        timestamp,
+                    });
+                }
+
+                self.total_fees_collected =
+                    self.total_fees_collected.saturating_sub(total_distributed);
+
+                Ok(total_distributed)
+            })
+        }
+
+        /// Get a beneficiary's share of fees in basis points
+        #[ink(message)]
+        pub fn get_fee_beneficiary_share(&self, beneficiary: AccountId) -> u32 {
+            self.fee_beneficiaries.get(beneficiary).unwrap_or(0)
+        }
+
+        /// Get all fee beneficiaries as (beneficiary, share_bp) pairs
+        #[ink(message)]
+        pub fn get_fee_beneficiaries(&self) -> Vec<(AccountId, u32)> {
+            self.fee_beneficiary_list
+                .iter()
+                .map(|beneficiary| {
+                    (
+                        *beneficiary,
+                        self.fee_beneficiaries.get(*beneficiary).unwrap_or(0),
+                    )
+                })
+                .collect()
+        }
+
+        /// Get the sum of all beneficiary shares in basis points
+        #[ink(message)]
+        pub fn get_total_beneficiary_share(&self) -> u32 {
+            self.total_beneficiary_share_bp
+        }
+
+        // ===== TOKEN CONTRACT INTEGRATION =====
+
+        /// Cross-contract call to transfer PSP22 tokens held by the portfolio
+        fn call_token_transfer(
+            &self,
+            token: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22::transfer"),
+                    ))
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+
+            self.handle_token_call_result("call_token_transfer", result)
+        }
+
+        /// Cross-contract call to pull PSP22 tokens using an allowance
+        fn call_token_transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            to: AccountId,
+            amount: u128,
+        ) -> Result<(), Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22::transfer_from"),
+                    ))
+                    .push_arg(from)
+                    .push_arg(to)
+                    .push_arg(amount)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+
+            self.handle_token_call_result("call_token_transfer_from", result)
+        }
+
+        /// Cross-contract call to read a token's total supply
+        fn call_token_total_supply(&self, token: AccountId) -> Result<u128, Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(ink::env::call::ExecutionInput::new(
+                    ink::env::call::Selector::new(ink::selector_bytes!("PSP22::total_supply")),
+                ))
+                .returns::<u128>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok(total_supply)) => Ok(total_supply),
+                _ => {
+                    self.emit_operation_failed("call_token_total_supply", "Token call failed");
+                    Err(Error::OracleCallFailed)
+                }
+            }
+        }
+
+        /// Cross-contract call to mint index tokens to the portfolio
+        fn call_token_mint(&self, token: AccountId, amount: u128) -> Result<(), Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22Mintable::mint"),
+                    ))
+                    .push_arg(amount),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+
+            self.handle_token_call_result("call_token_mint", result)
+        }
+
+        /// Cross-contract call to burn index tokens held by the portfolio
+        fn call_token_burn(&self, token: AccountId, amount: u128) -> Result<(), Error> {
+            let result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
+                .call(token)
+                .call_v1()
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("PSP22Burnable::burn"),
+                    ))
+                    .push_arg(amount),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke();
+
+            self.handle_token_call_result("call_token_burn", result)
+        }
+
+        /// Map the outcome of a token cross-contract call onto portfolio errors
+        /// Balance and allowance shortfalls keep distinct errors.
+        fn handle_token_call_result(
+            &self,
+            operation: &str,
+            result: ink::env::Result<ink::MessageResult<Result<(), PSP22Error>>>,
+        ) -> Result<(), Error> {
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                Ok(Ok(Err(token_error))) => {
+                    self.emit_operation_failed(
+                        operation,
+                        &format!("Token contract returned {:?}", token_error),
+                    );
+                    Err(Error::from(token_error))
+                }
+                _ => {
+                    self.emit_operation_failed(operation, "Token call failed");
+                    Err(Error::OracleCallFailed)
+                }
+            }
+        }
+
+        // ===== ERROR HANDLING HELPER =====
+
+        /// Emit operation failed event for monitoring
+        fn emit_operation_failed(&self, operation: &str, error: &str) {
+            self.env().emit_event(OperationFailed {
+                operation: String::from(operation),
+                error: String::from(error),
+                caller: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+        }
+    }
+
+    impl shared::Portfolio for Portfolio {
+        /// Get net asset value per whole W3PI share in plancks
+        #[ink(message)]
+        fn get_nav_per_share(&self) -> Result<u128, Error> {
+            Portfolio::get_nav_per_share(self)
+        }
+
+        /// Get the last computed index value
+        #[ink(message)]
+        fn get_current_index_value(&self) -> u128 {
+            Portfolio::get_current_index_value(self)
+        }
+
+        /// Check whether the portfolio is emergency paused
+        #[ink(message)]
+        fn is_emergency_paused(&self) -> bool {
+            Portfolio::is_emergency_paused(self)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn assert_implements_portfolio<T: shared::Portfolio>() {}
+
+        #[test]
+        fn portfolio_implements_shared_trait() {
+            assert_implements_portfolio::<Portfolio>();
+        }
+
+        #[ink::test]
+        fn emergency_controller_can_pause_but_not_configure() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut portfolio = Portfolio::new();
+            portfolio
+                .grant_role(Role::EmergencyController, accounts.bob)
+                .unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(portfolio.emergency_pause(String::from("incident")), Ok(()));
+            assert!(portfolio.is_emergency_paused());
+            assert_eq!(portfolio.set_max_tokens(10), Err(Error::Unauthorized));
+            assert_eq!(
+                portfolio.resume_operations(String::from("resolved")),
+                Ok(())
+            );
+            assert!(!portfolio.is_emergency_paused());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                portfolio.emergency_pause(String::from("incident")),
+                Err(Error::UnauthorizedRole)
+            );
+        }
+
+        #[test]
+        fn price_impact_cap_excludes_dex_fee() {
+            // 2% tolerance on top of a 0.3% fee
+            assert_eq!(Portfolio::price_impact_cap_bp(200, 30), 230);
+            assert_eq!(Portfolio::price_impact_cap_bp(9_990, 30), 10_000);
+        }
+
+        #[test]
+        fn share_deposit_keeps_nav_per_share() {
+            let portfolio_value = 1_234_567 * INDEX_TOKEN_UNIT;
+            let total_supply = 9_876 * INDEX_TOKEN_UNIT;
+            let nav_before = Portfolio::nav_per_share(portfolio_value, total_supply).unwrap();
+
+            let deposit_value = 55_555 * INDEX_TOKEN_UNIT;
+            let shares = Portfolio::shares_for_value(deposit_value, nav_before).unwrap();
+            let nav_after =
+                Portfolio::nav_per_share(portfolio_value + deposit_value, total_supply + shares)
+                    .unwrap();
+
+            assert_eq!(nav_after, nav_before);
+        }
+
+        #[test]
+        fn redeemed_value_sums_amounts_sent_at_price() {
+            let usdc_returned = 500;
+            let value = Portfolio::add_priced_amount(usdc_returned, 3, 100).unwrap();
+            assert_eq!(Portfolio::add_priced_amount(value, 2, 50), Ok(900));
+            assert_eq!(
+                Portfolio::add_priced_amount(1, u128::MAX, 1),
+                Err(Error::ArithmeticOverflow)
+            );
+        }
+
+        #[ink::test]
+        fn keeper_updates_only_stale_index() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut portfolio = Portfolio::new();
+            portfolio.set_index_tracking(true).unwrap();
+            portfolio.set_keeper(Some(accounts.bob)).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(portfolio.update_index_value(), Err(Error::UnauthorizedRole));
+            assert_eq!(
+                portfolio.keeper_update_index(),
+                Err(Error::InvalidParameter)
+            );
+
+            let stale_at = DEFAULT_INDEX_STALENESS_MS + 1;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(stale_at);
+            assert!(portfolio.is_index_value_stale());
+            assert_eq!(
+                portfolio.keeper_update_index(),
+                Ok(portfolio.get_current_index_value())
+            );
+            assert!(!portfolio.is_index_value_stale());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(portfolio.keeper_update_index(), Err(Error::Unauthorized));
+        }
+
+        #[test]
+        fn health_valuation_counts_unpriced_holdings_as_stale() {
+            let holding = |amount: u128| TokenHolding {
+                amount,
+                target_weight_bp: 2_500,
+                last_rebalance: 0,
+                fees_collected: 0,
+            };
+            let observations = vec![
+                (1, holding(10), Some(5), false),
+                (2, holding(10), Some(0), false),
+                (3, holding(10), None, false),
+                (4, holding(10), Some(7), true),
+            ];
+
+            let (valuations, total_value, stale_prices) =
+                Portfolio::value_observed_holdings(1_000, observations).unwrap();
+            assert_eq!(valuations, vec![(1, holding(10), 5)]);
+            assert_eq!(total_value, 1_050);
+            assert_eq!(stale_prices, 3);
+        }
+
+        #[ink::test]
+        fn exported_holdings_import_into_fresh_portfolio() {
+            let holdings = vec![
+                (
+                    1,
+                    TokenHolding {
+                        amount: 500,
+                        target_weight_bp: 6000,
+                        last_rebalance: 42,
+                        fees_collected: 7,
+                    },
+                ),
+                (
+                    2,
+                    TokenHolding {
+                        amount: 300,
+                        target_weight_bp: 4000,
+                        last_rebalance: 43,
+                        fees_collected: 0,
+                    },
+                ),
+            ];
+
+            let mut old_portfolio = Portfolio::new();
+            assert_eq!(old_portfolio.import_holdings(holdings.clone()), Ok(2));
+            let exported = [
+                old_portfolio.export_holdings(0, 1),
+                old_portfolio.export_holdings(1, 1),
+            ]
+            .concat();
+            assert_eq!(exported, holdings);
+            assert!(old_portfolio.export_holdings(2, 1).is_empty());
+
+            let mut new_portfolio = Portfolio::new();
+            assert_eq!(new_portfolio.import_holdings(exported), Ok(2));
+            assert_eq!(
+                new_portfolio.get_token_holding(1),
+                Some(holdings[0].1.clone())
+            );
+            assert_eq!(new_portfolio.get_token_fees_collected(1), 7);
+            assert_eq!(new_portfolio.get_total_target_weight(), 10000);
+        }
+
+        #[ink::test]
+        fn equal_weights_sum_to_total() {
+            let mut portfolio = Portfolio::new();
+            assert_eq!(portfolio.set_equal_weights(), Err(Error::InvalidParameter));
+
+            portfolio
+                .import_holdings(
+                    (1..=3)
+                        .map(|token_id| (token_id, TokenHolding::default()))
+                        .collect(),
+                )
+                .unwrap();
+
+            assert_eq!(portfolio.set_equal_weights(), Ok(3333));
+            assert_eq!(
+                portfolio.get_all_target_weights(),
+                vec![(1, 3334), (2, 3333), (3, 3333)]
+            );
+            assert_eq!(portfolio.get_total_target_weight(), 10000);
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            portfolio
+                .grant_role(Role::TokenUpdater, accounts.bob)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(portfolio.set_equal_weights(), Err(Error::Unauthorized));
+            assert_eq!(portfolio.set_market_cap_weights(), Err(Error::Unauthorized));
+        }
+
+        #[test]
+        fn cap_weights_follow_market_cap_share() {
+            assert_eq!(
+                Portfolio::calculate_cap_weights(&[300, 100, 100], 10000),
+                Ok(vec![6000, 2000, 2000])
+            );
+            // Rounding remainder goes to the largest weight
+            assert_eq!(
+                Portfolio::calculate_cap_weights(&[1, 1, 1], 10000),
+                Ok(vec![3334, 3333, 3333])
+            );
+        }
+
+        #[test]
+        fn cap_weights_redistribute_excess_over_cap() {
+            // 7000 is capped at 4000; the 3000 excess is split 2:1 over the others
+            assert_eq!(
+                Portfolio::calculate_cap_weights(&[700, 200, 100], 4000),
+                Ok(vec![4000, 4000, 2000])
+            );
+            // The second pass can cap another token
+            assert_eq!(
+                Portfolio::calculate_cap_weights(&[800, 150, 50], 4000),
+                Ok(vec![4000, 4000, 2000])
+            );
+        }
+
+        #[ink::test]
+        fn import_holdings_rejects_weight_over_total_and_after_init() {
+            let mut portfolio = Portfolio::new();
+            let holding = TokenHolding {
+                amount: 1,
+                target_weight_bp: 6000,
+                ..Default::default()
+            };
+            assert_eq!(
+                portfolio.import_holdings(vec![(1, holding.clone()), (2, holding.clone())]),
+                Err(Error::WeightExceeded)
+            );
+
+            portfolio.base_portfolio_value = 1;
+            assert_eq!(
+                portfolio.import_holdings(vec![(1, holding)]),
+                Err(Error::InvalidParameter)
+            );
+        }
+
+        #[ink::test]
+        fn sweep_token_refuses_usdc_and_bad_input() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let usdc = AccountId::from([0x55; 32]);
+            let mut portfolio = Portfolio::new();
+            portfolio.set_usdc_contract(usdc).unwrap();
+
+            assert_eq!(
+                portfolio.sweep_token(usdc, accounts.bob, 100),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                portfolio.sweep_token(accounts.django, accounts.bob, 0),
+                Err(Error::AmountZero)
+            );
+            assert_eq!(
+                portfolio.sweep_token(accounts.django, AccountId::from([0u8; 32]), 100),
+                Err(Error::ZeroAddress)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                portfolio.sweep_token(accounts.django, accounts.bob, 100),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn staleness_threshold_is_bounded() {
+            let mut portfolio = Portfolio::new();
+            portfolio.set_index_tracking(true).unwrap();
+
+            assert_eq!(
+                portfolio.set_cache_staleness_threshold(MIN_CACHE_STALENESS_MS - 1),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                portfolio.set_cache_staleness_threshold(MAX_CACHE_STALENESS_MS + 1),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                portfolio.set_index_staleness_threshold(MIN_INDEX_STALENESS_MS - 1),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                portfolio.set_index_staleness_threshold(MAX_INDEX_STALENESS_MS + 1),
+                Err(Error::InvalidParameter)
+            );
+
+            // The cache window does not move the index window
+            assert_eq!(
+                portfolio.set_cache_staleness_threshold(MIN_CACHE_STALENESS_MS),
+                Ok(())
+            );
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                MIN_INDEX_STALENESS_MS + 1,
+            );
+            assert!(!portfolio.is_index_value_stale());
+
+            assert_eq!(
+                portfolio.set_index_staleness_threshold(MIN_INDEX_STALENESS_MS),
+                Ok(())
+            );
+            assert_eq!(
+                portfolio.get_index_staleness_threshold(),
+                MIN_INDEX_STALENESS_MS
+            );
+            assert!(portfolio.is_index_value_stale());
         }
     }
 }
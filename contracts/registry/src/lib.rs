@@ -4,29 +4,16 @@
 
 #[ink::contract]
 mod registry {
+    use ink::codegen::TraitCallBuilder;
     use ink::prelude::string::String;
     use ink::prelude::vec; // Import the vec! macro
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
+    pub use shared::tier::Tier;
     use shared::{EnrichedTokenData, Error, Role, TokenData};
 
     // ===== TIER SYSTEM DATA STRUCTURES =====
 
-    /// Enhanced tier classification for tokens
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy, Default)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    pub enum Tier {
-        #[default]
-        None, // Below minimum thresholds
-        Tier1, // $50M market cap + $5M volume
-        Tier2, // $250M market cap + $25M volume
-        Tier3, // $500M market cap + $50M volume
-        Tier4, // $2B market cap + $200M volume
-    }
-
     /// Tier threshold configuration (in USD values)
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
     #[cfg_attr(
@@ -83,6 +70,21 @@ mod registry {
         /// Tier management
         pub tier_change_timestamp: Option<u64>,
         pub pending_tier_change: Option<Tier>,
+        /// Secondary oracle used when the primary does not respond
+        pub backup_oracle: Option<AccountId>,
+    }
+
+    /// Human-readable token identity
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TokenMetadata {
+        pub name: String,
+        pub symbol: String,
+        /// Token precision, used to normalize amounts across tokens
+        pub decimals: u8,
     }
 
     impl From<TokenData> for EnhancedTokenData {
@@ -95,6 +97,7 @@ mod registry {
                 tier: Tier::None, // Will be calculated
                 tier_change_timestamp: None,
                 pending_tier_change: None,
+                backup_oracle: None,
             }
         }
     }
@@ -107,10 +110,14 @@ mod registry {
         tokens: Mapping<u32, EnhancedTokenData>,
         /// Mapping from token contract to token ID (for duplicate prevention)
         token_contract_to_id: Mapping<AccountId, u32>,
+        /// Token name, symbol and decimals: token_id -> metadata
+        token_metadata: Mapping<u32, TokenMetadata>,
         /// Role-based access control: (Role, AccountId) -> bool
         role_members: Mapping<(Role, AccountId), bool>,
         /// Next available token ID
         next_token_id: u32,
+        /// IDs of currently registered tokens (for iteration)
+        active_token_ids: Vec<u32>,
         /// Registry owner (super-admin)
         owner: AccountId,
 
@@ -121,14 +128,28 @@ mod registry {
         tier_thresholds: TierThresholds,
         /// Cached tier distribution for gas optimization
         tier_distribution: Mapping<Tier, u32>,
+        /// Committed tier changes per token: token_id -> [(tier, timestamp)], newest last
+        tier_history: Mapping<u32, Vec<(Tier, u64)>>,
         /// Last time active tier was changed
         last_tier_change: Option<u64>,
+        /// USD to plancks rate used by the most recent tier calculation
+        last_used_usd_rate: u128,
+        /// When `last_used_usd_rate` was observed
+        last_usd_rate_timestamp: u64,
         /// DOT/USD oracle contract for conversion rates
         dot_usd_oracle: Option<AccountId>,
 
         // ===== NEW GRACE PERIOD CONFIGURATION =====
         /// Adjustable grace period in milliseconds (default: 90 days)
         grace_period_ms: u64,
+        /// Reject update_token entirely when the token's oracle price is stale
+        strict_staleness: bool,
+
+        // ===== TIER SHIFT POLICY =====
+        /// Percentage of tokens that must qualify for a higher tier to shift (1-100)
+        tier_shift_threshold_percent: u32,
+        /// Minimum registered tokens before the threshold rule applies
+        min_tokens_for_tier_shift: u32,
     }
 
     // ===== ENHANCED EVENTS =====
@@ -165,6 +186,34 @@ mod registry {
         removed_by: AccountId,
     }
 
+    #[ink(event)]
+    pub struct TokenMetadataUpdated {
+        #[ink(topic)]
+        token_id: u32,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        updated_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct WeightsNormalized {
+        old_total: u32,
+        new_total: u32,
+        tokens_adjusted: u32,
+        normalized_by: AccountId,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct BackupOracleUpdated {
+        #[ink(topic)]
+        token_id: u32,
+        old_backup: Option<AccountId>,
+        new_backup: AccountId,
+        updated_by: AccountId,
+    }
+
     #[ink(event)]
     pub struct TokenTierChanged {
         #[ink(topic)]
@@ -188,6 +237,22 @@ mod registry {
         total_tokens: u32,
     }
 
+    #[ink(event)]
+    pub struct TierShiftEvaluated {
+        active_tier: Tier,
+        candidate_tier: Option<Tier>, // Higher tier with the largest share of tokens
+        candidate_percentage: u32,
+        threshold_met: bool,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct UsdRateObserved {
+        usd_to_plancks_rate: u128,
+        from_oracle: bool, // False when the conservative fallback rate was used
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct TierThresholdsUpdated {
         updated_by: AccountId,
@@ -206,6 +271,14 @@ mod registry {
 
     // ===== NEW GRACE PERIOD EVENTS =====
 
+    #[ink(event)]
+    pub struct TierShiftPolicyUpdated {
+        threshold_percent: u32,
+        min_tokens: u32,
+        updated_by: AccountId,
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct GracePeriodUpdated {
         old_period_ms: u64,
@@ -214,6 +287,15 @@ mod registry {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct TierRecalcSkipped {
+        #[ink(topic)]
+        token_id: u32,
+        current_tier: Tier,
+        reason: String, // "stale_price"
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct EmergencyTierOverride {
         #[ink(topic)]
@@ -265,11 +347,23 @@ mod registry {
     /// Maximum grace period: 365 days
     const MAX_GRACE_PERIOD_MS: u64 = 365 * 24 * 60 * 60 * 1000; // 31,536,000,000 ms
 
-    /// Minimum tokens required for 80% rule calculation
-    const MIN_TOKENS_FOR_TIER_SHIFT: u32 = 5;
+    /// Default minimum tokens required for 80% rule calculation
+    const DEFAULT_MIN_TOKENS_FOR_TIER_SHIFT: u32 = 5;
+
+    /// Default percentage threshold for automatic tier shifting
+    const DEFAULT_TIER_SHIFT_THRESHOLD_PERCENT: u32 = 80;
+
+    /// Maximum token IDs scanned per paged query
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Maximum supported token decimals (keeps price scaling within u128)
+    const MAX_TOKEN_DECIMALS: u8 = 18;
 
-    /// Percentage threshold for automatic tier shifting
-    const TIER_SHIFT_THRESHOLD_PERCENT: u32 = 80;
+    /// Target sum of all token investment weights (100% in basis points)
+    const TOTAL_WEIGHT_BP: u32 = 10_000;
+
+    /// Maximum tier history entries kept per token
+    const MAX_TIER_HISTORY: usize = 20;
 
     impl Default for Registry {
         fn default() -> Self {
@@ -284,15 +378,23 @@ mod registry {
             let mut registry = Self {
                 tokens: Mapping::default(),
                 token_contract_to_id: Mapping::default(),
+                token_metadata: Mapping::default(),
                 role_members: Mapping::default(),
                 next_token_id: 1,
+                active_token_ids: Vec::new(),
                 owner: Self::env().caller(),
                 active_tier: Tier::Tier1, // Start with Tier1
                 tier_thresholds: TierThresholds::default(),
                 tier_distribution: Mapping::default(),
+                tier_history: Mapping::default(),
                 last_tier_change: None,
+                last_used_usd_rate: 0,
+                last_usd_rate_timestamp: 0,
                 dot_usd_oracle: None, // Must be set by owner after deployment
                 grace_period_ms: DEFAULT_GRACE_PERIOD_MS, // 90 days default
+                strict_staleness: false,
+                tier_shift_threshold_percent: DEFAULT_TIER_SHIFT_THRESHOLD_PERCENT,
+                min_tokens_for_tier_shift: DEFAULT_MIN_TOKENS_FOR_TIER_SHIFT,
             };
 
             // Initialize tier distribution cache
@@ -360,54 +462,126 @@ mod registry {
         ) -> Result<u32, Error> {
             self.ensure_role(Role::TokenManager)?;
 
-            // Input validation
-            if token_contract == AccountId::from([0u8; 32]) {
-                self.emit_operation_failed("add_token", Error::ZeroAddress);
-                return Err(Error::ZeroAddress);
+            self.validate_new_token("add_token", token_contract, oracle_contract)?;
+
+            let token_id = self.insert_new_token(token_contract, oracle_contract);
+
+            // Check for automatic tier shift
+            self.check_and_execute_auto_tier_shift();
+
+            Ok(token_id)
+        }
+
+        /// Add several tokens in one transaction with automatic tier calculation
+        /// All entries are validated before any is stored, so an invalid entry
+        /// rejects the whole batch.
+        #[ink(message)]
+        pub fn add_tokens_batch(
+            &mut self,
+            entries: Vec<(AccountId, AccountId)>, // (token_contract, oracle_contract)
+        ) -> Result<Vec<u32>, Error> {
+            self.ensure_role(Role::TokenManager)?;
+
+            if entries.is_empty() {
+                self.emit_operation_failed("add_tokens_batch", Error::InvalidParameter);
+                return Err(Error::InvalidParameter);
             }
 
-            if oracle_contract == AccountId::from([0u8; 32]) {
-                self.emit_operation_failed("add_token", Error::ZeroAddress);
+            for (index, (token_contract, oracle_contract)) in entries.iter().enumerate() {
+                self.validate_new_token("add_tokens_batch", *token_contract, *oracle_contract)?;
+
+                // Check for duplicates within the batch
+                if entries[..index]
+                    .iter()
+                    .any(|(earlier, _)| earlier == token_contract)
+                {
+                    self.emit_operation_failed("add_tokens_batch", Error::TokenAlreadyExists);
+                    return Err(Error::TokenAlreadyExists);
+                }
+            }
+
+            let token_ids = entries
+                .into_iter()
+                .map(|(token_contract, oracle_contract)| {
+                    self.insert_new_token(token_contract, oracle_contract)
+                })
+                .collect();
+
+            // Check for automatic tier shift once for the whole batch
+            self.check_and_execute_auto_tier_shift();
+
+            Ok(token_ids)
+        }
+
+        /// Validate a token/oracle pair before registration
+        fn validate_new_token(
+            &self,
+            operation: &str,
+            token_contract: AccountId,
+            oracle_contract: AccountId,
+        ) -> Result<(), Error> {
+            // Input validation
+            if token_contract == AccountId::from([0u8; 32])
+                || oracle_contract == AccountId::from([0u8; 32])
+            {
+                self.emit_operation_failed(operation, Error::ZeroAddress);
                 return Err(Error::ZeroAddress);
             }
 
             // Check for duplicates
             if self.token_contract_to_id.contains(token_contract) {
-                self.emit_operation_failed("add_token", Error::TokenAlreadyExists);
+                self.emit_operation_failed(operation, Error::TokenAlreadyExists);
                 return Err(Error::TokenAlreadyExists);
             }
 
+            Ok(())
+        }
+
+        /// Store a validated token with its initial tier and return the assigned ID
+        fn insert_new_token(
+            &mut self,
+            token_contract: AccountId,
+            oracle_contract: AccountId,
+        ) -> u32 {
+            // Calculate initial tier
+            self.observe_usd_rate();
+            let initial_tier = self
+                .calculate_token_tier_internal(token_contract, oracle_contract)
+                .unwrap_or(Tier::None);
+
+            self.store_token(token_contract, oracle_contract, initial_tier)
+        }
+
+        /// Store a token under the next ID with an already calculated tier
+        fn store_token(
+            &mut self,
+            token_contract: AccountId,
+            oracle_contract: AccountId,
+            initial_tier: Tier,
+        ) -> u32 {
             let token_id = self.next_token_id;
 
             // Create enhanced token data
-            let mut enhanced_token_data = EnhancedTokenData {
+            let enhanced_token_data = EnhancedTokenData {
                 token_contract,
                 oracle_contract,
                 balance: 0,
                 weight_investment: 0,
-                tier: Tier::None, // Will be calculated
+                tier: initial_tier,
                 tier_change_timestamp: None,
                 pending_tier_change: None,
+                backup_oracle: None,
             };
 
-            // Calculate initial tier
-            let initial_tier = self
-                .calculate_token_tier_internal(token_contract, oracle_contract)
-                .unwrap_or(Tier::None);
-
-            enhanced_token_data.tier = initial_tier;
-
             // Store token data
             self.tokens.insert(token_id, &enhanced_token_data);
             self.token_contract_to_id.insert(token_contract, &token_id);
             self.next_token_id = self.next_token_id.saturating_add(1);
+            self.active_token_ids.push(token_id);
 
             // Update tier distribution cache
             self.increment_tier_count(initial_tier);
 
-            // Check for automatic tier shift
-            self.check_and_execute_auto_tier_shift();
-
             self.env().emit_event(TokenAdded {
                 token_id,
                 token_contract,
@@ -416,7 +590,7 @@ mod registry {
                 added_by: self.env().caller(),
             });
 
-            Ok(token_id)
+            token_id
         }
 
         /// Update token balance and investment data with automatic tier recalculation
@@ -446,13 +620,32 @@ mod registry {
             token_data.balance = balance;
             token_data.weight_investment = weight_investment;
 
-            // Recalculate tier based on current market data
-            let new_tier = self
-                .calculate_token_tier_internal(
+            let price_is_stale = self
+                .query_price_staleness(token_data.token_contract, token_data.oracle_contract)
+                == Some(true);
+
+            if price_is_stale && self.strict_staleness {
+                self.emit_operation_failed("update_token", Error::OracleCallFailed);
+                return Err(Error::OracleCallFailed);
+            }
+
+            // Recalculate tier based on current market data, never on stale prices
+            let new_tier = if price_is_stale {
+                self.env().emit_event(TierRecalcSkipped {
+                    token_id,
+                    current_tier: old_tier,
+                    reason: "stale_price".into(),
+                    timestamp: self.env().block_timestamp(),
+                });
+                old_tier
+            } else {
+                self.observe_usd_rate();
+                self.calculate_token_tier_internal(
                     token_data.token_contract,
                     token_data.oracle_contract,
                 )
-                .unwrap_or(token_data.tier);
+                .unwrap_or(token_data.tier)
+            };
 
             // Handle tier change with grace period
             if new_tier != old_tier {
@@ -474,6 +667,81 @@ mod registry {
             Ok(())
         }
 
+        /// Get the sum of investment weights over all registered tokens
+        #[ink(message)]
+        pub fn get_total_investment_weight(&self) -> u32 {
+            self.active_token_ids
+                .iter()
+                .filter_map(|&token_id| self.tokens.get(token_id))
+                .fold(0u32, |total, data| {
+                    total.saturating_add(data.weight_investment)
+                })
+        }
+
+        /// Rescale all investment weights proportionally so they sum to 10000 (owner only)
+        /// Rounding dust goes to the token with the largest weight.
+        #[ink(message)]
+        pub fn normalize_weights(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let old_total = self.get_total_investment_weight();
+            if old_total == 0 {
+                self.emit_operation_failed("normalize_weights", Error::InvalidParameter);
+                return Err(Error::InvalidParameter);
+            }
+
+            let mut scaled: Vec<(u32, EnhancedTokenData)> = Vec::new();
+            let mut new_total = 0u32;
+            let mut largest: Option<(usize, u32)> = None;
+
+            for token_id in self.active_token_ids.clone() {
+                if let Some(mut token_data) = self.tokens.get(token_id) {
+                    let old_weight = token_data.weight_investment;
+                    let new_weight = (old_weight as u64)
+                        .saturating_mul(TOTAL_WEIGHT_BP as u64)
+                        .checked_div(old_total as u64)
+                        .unwrap_or(0) as u32;
+
+                    if largest.map_or(true, |(_, weight)| old_weight > weight) {
+                        largest = Some((scaled.len(), old_weight));
+                    }
+
+                    token_data.weight_investment = new_weight;
+                    new_total = new_total.saturating_add(new_weight);
+                    scaled.push((token_id, token_data));
+                }
+            }
+
+            if let Some((index, _)) = largest {
+                let dust = TOTAL_WEIGHT_BP.saturating_sub(new_total);
+                if let Some((_, token_data)) = scaled.get_mut(index) {
+                    token_data.weight_investment =
+                        token_data.weight_investment.saturating_add(dust);
+                }
+            }
+
+            let mut tokens_adjusted = 0u32;
+            for (token_id, token_data) in scaled.iter() {
+                let changed = self.tokens.get(*token_id).map_or(false, |old| {
+                    old.weight_investment != token_data.weight_investment
+                });
+                if changed {
+                    self.tokens.insert(*token_id, token_data);
+                    tokens_adjusted = tokens_adjusted.saturating_add(1);
+                }
+            }
+
+            self.env().emit_event(WeightsNormalized {
+                old_total,
+                new_total: TOTAL_WEIGHT_BP,
+                tokens_adjusted,
+                normalized_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(())
+        }
+
         /// Remove a token from the registry
         #[ink(message)]
         pub fn remove_token(&mut self, token_id: u32) -> Result<(), Error> {
@@ -487,9 +755,14 @@ mod registry {
             let token_contract = token_data.token_contract;
             let tier = token_data.tier;
 
-            // Remove from both mappings
+            // Remove from all mappings
             self.tokens.remove(token_id);
             self.token_contract_to_id.remove(token_contract);
+            self.token_metadata.remove(token_id);
+            self.tier_history.remove(token_id);
+            if let Some(pos) = self.active_token_ids.iter().position(|&x| x == token_id) {
+                self.active_token_ids.remove(pos);
+            }
 
             // Update tier distribution cache
             self.decrement_tier_count(tier);
@@ -507,6 +780,107 @@ mod registry {
             Ok(())
         }
 
+        /// Set token name, symbol and decimals
+        #[ink(message)]
+        pub fn set_token_metadata(
+            &mut self,
+            token_id: u32,
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Result<(), Error> {
+            self.ensure_role(Role::TokenManager)?;
+
+            if !self.tokens.contains(token_id) {
+                self.emit_operation_failed("set_token_metadata", Error::TokenNotFound);
+                return Err(Error::TokenNotFound);
+            }
+
+            if name.is_empty() || symbol.is_empty() || decimals > MAX_TOKEN_DECIMALS {
+                self.emit_operation_failed("set_token_metadata", Error::InvalidParameter);
+                return Err(Error::InvalidParameter);
+            }
+
+            self.token_metadata.insert(
+                token_id,
+                &TokenMetadata {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                    decimals,
+                },
+            );
+
+            self.env().emit_event(TokenMetadataUpdated {
+                token_id,
+                name,
+                symbol,
+                decimals,
+                updated_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Set the backup oracle used when a token's primary oracle is unavailable
+        #[ink(message)]
+        pub fn set_backup_oracle(&mut self, token_id: u32, oracle: AccountId) -> Result<(), Error> {
+            self.ensure_role(Role::TokenManager)?;
+
+            if oracle == AccountId::from([0u8; 32]) {
+                self.emit_operation_failed("set_backup_oracle", Error::ZeroAddress);
+                return Err(Error::ZeroAddress);
+            }
+
+            let mut token_data = self.tokens.get(token_id).ok_or_else(|| {
+                self.emit_operation_failed("set_backup_oracle", Error::TokenNotFound);
+                Error::TokenNotFound
+            })?;
+
+            if oracle == token_data.oracle_contract {
+                self.emit_operation_failed("set_backup_oracle", Error::InvalidParameter);
+                return Err(Error::InvalidParameter);
+            }
+
+            let old_backup = token_data.backup_oracle;
+            token_data.backup_oracle = Some(oracle);
+            self.tokens.insert(token_id, &token_data);
+
+            self.env().emit_event(BackupOracleUpdated {
+                token_id,
+                old_backup,
+                new_backup: oracle,
+                updated_by: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Check whether the primary and backup oracles currently respond
+        /// Returns (primary_ok, backup_ok); a missing backup reports false.
+        #[ink(message)]
+        pub fn get_oracle_health(&self, token_id: u32) -> (bool, bool) {
+            let token_data = match self.tokens.get(token_id) {
+                Some(data) => data,
+                None => return (false, false),
+            };
+
+            let primary_ok = self
+                .query_market_data(token_data.token_contract, token_data.oracle_contract)
+                .is_some();
+            let backup_ok = token_data.backup_oracle.map_or(false, |backup| {
+                self.query_market_data(token_data.token_contract, backup)
+                    .is_some()
+            });
+
+            (primary_ok, backup_ok)
+        }
+
+        /// Get token name, symbol and decimals
+        #[ink(message)]
+        pub fn get_token_metadata(&self, token_id: u32) -> Option<TokenMetadata> {
+            self.token_metadata.get(token_id)
+        }
+
         // ===== TIER CLASSIFICATION SYSTEM =====
 
         /// Calculate tier for a token based on market cap and volume
@@ -535,15 +909,35 @@ mod registry {
             Some(self.calculate_tier_from_values(market_cap, volume))
         }
 
-        /// Calculate tier based on market cap and volume values
-        fn calculate_tier_from_values(&self, market_cap: u128, volume: u128) -> Tier {
-            // Get DOT/USD conversion rate from oracle
-            let usd_to_plancks_rate = self.get_usd_to_plancks_rate().unwrap_or({
+        /// USD to plancks rate for tier math and whether it came from the oracle
+        fn effective_usd_rate(&self) -> (u128, bool) {
+            match self.get_usd_to_plancks_rate() {
+                Some(rate) => (rate, true),
                 // Fallback: use a conservative default if oracle fails
                 // 1 DOT = $5 USD (conservative estimate), 1 DOT = 10^10 plancks
                 // $1 USD = 0.2 DOT = 2 × 10^9 plancks
-                2_000_000_000u128
+                None => (2_000_000_000u128, false),
+            }
+        }
+
+        /// Record the rate tier calculations in this call will use
+        fn observe_usd_rate(&mut self) {
+            let (usd_to_plancks_rate, from_oracle) = self.effective_usd_rate();
+            let timestamp = self.env().block_timestamp();
+            self.last_used_usd_rate = usd_to_plancks_rate;
+            self.last_usd_rate_timestamp = timestamp;
+
+            self.env().emit_event(UsdRateObserved {
+                usd_to_plancks_rate,
+                from_oracle,
+                timestamp,
             });
+        }
+
+        /// Calculate tier based on market cap and volume values
+        fn calculate_tier_from_values(&self, market_cap: u128, volume: u128) -> Tier {
+            // Get DOT/USD conversion rate from oracle
+            let usd_to_plancks_rate = self.effective_usd_rate().0;
 
             let thresholds = &self.tier_thresholds;
 
@@ -595,6 +989,7 @@ mod registry {
             let old_tier = token_data.tier;
 
             // Calculate new tier
+            self.observe_usd_rate();
             let new_tier = self
                 .calculate_token_tier_internal(
                     token_data.token_contract,
@@ -640,6 +1035,7 @@ mod registry {
             token_data.pending_tier_change = None; // Clear any pending changes
 
             self.tokens.insert(token_id, &token_data);
+            self.record_tier_history(token_id, new_tier, self.env().block_timestamp());
 
             // Emit emergency override event
             self.env().emit_event(EmergencyTierOverride {
@@ -682,6 +1078,7 @@ mod registry {
             let token_data = self.tokens.get(token_id).ok_or(Error::TokenNotFound)?;
 
             // Calculate what tier should be based on current market data
+            self.observe_usd_rate();
             let calculated_tier = self
                 .calculate_token_tier_internal(
                     token_data.token_contract,
@@ -736,6 +1133,70 @@ mod registry {
             Ok(())
         }
 
+        /// Set whether stale oracle prices reject update_token entirely (owner only)
+        #[ink(message)]
+        pub fn set_strict_staleness(&mut self, strict: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.strict_staleness = strict;
+            Ok(())
+        }
+
+        /// Check whether stale oracle prices reject update_token
+        #[ink(message)]
+        pub fn is_strict_staleness(&self) -> bool {
+            self.strict_staleness
+        }
+
+        /// Set the percentage of tokens that must qualify for a tier shift (owner only)
+        #[ink(message)]
+        pub fn set_tier_shift_threshold(&mut self, percent: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if !(1..=100).contains(&percent) {
+                return Err(Error::InvalidParameter);
+            }
+
+            self.tier_shift_threshold_percent = percent;
+            self.emit_tier_shift_policy_updated();
+
+            Ok(())
+        }
+
+        /// Set the minimum token count before tier shifts are considered (owner only)
+        #[ink(message)]
+        pub fn set_min_tokens_for_shift(&mut self, count: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if count == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            self.min_tokens_for_tier_shift = count;
+            self.emit_tier_shift_policy_updated();
+
+            Ok(())
+        }
+
+        /// Get the tier shift policy
+        /// Returns: (threshold_percent, min_tokens)
+        #[ink(message)]
+        pub fn get_tier_shift_policy(&self) -> (u32, u32) {
+            (
+                self.tier_shift_threshold_percent,
+                self.min_tokens_for_tier_shift,
+            )
+        }
+
+        /// Emit the current tier shift policy
+        fn emit_tier_shift_policy_updated(&self) {
+            self.env().emit_event(TierShiftPolicyUpdated {
+                threshold_percent: self.tier_shift_threshold_percent,
+                min_tokens: self.min_tokens_for_tier_shift,
+                updated_by: self.env().caller(),
+                timestamp: self.env().block_timestamp(),
+            });
+        }
+
         /// Get current grace period duration in milliseconds
         #[ink(message)]
         pub fn get_grace_period(&self) -> u64 {
@@ -797,10 +1258,10 @@ mod registry {
         pub fn refresh_all_tiers(&mut self) -> Result<u32, Error> {
             self.ensure_role(Role::TokenManager)?;
 
-            let total_tokens = self.get_token_count();
             let mut updated_count = 0u32;
+            self.observe_usd_rate();
 
-            for token_id in 1..=total_tokens {
+            for token_id in self.active_token_ids.clone() {
                 if let Some(mut token_data) = self.tokens.get(token_id) {
                     let old_tier = token_data.tier;
 
@@ -831,8 +1292,7 @@ mod registry {
             let current_time = self.env().block_timestamp();
             let mut processed_count = 0u32;
 
-            let total_tokens = self.get_token_count();
-            for token_id in 1..=total_tokens {
+            for token_id in self.active_token_ids.clone() {
                 if let Some(mut token_data) = self.tokens.get(token_id) {
                     if let (Some(pending_tier), Some(change_time)) = (
                         token_data.pending_tier_change,
@@ -852,6 +1312,7 @@ mod registry {
                             token_data.tier_change_timestamp = Some(current_time);
 
                             self.tokens.insert(token_id, &token_data);
+                            self.record_tier_history(token_id, pending_tier, current_time);
                             processed_count = processed_count.saturating_add(1);
 
                             // Emit tier change event
@@ -884,6 +1345,18 @@ mod registry {
 
         // ===== TIER DISTRIBUTION & 80% RULE =====
 
+        /// Get the index's tier makeup in one call from the distribution cache
+        /// Returns: (active_tier, total_tokens, distribution including Tier::None, last_tier_change)
+        #[ink(message)]
+        pub fn get_index_summary(&self) -> (Tier, u32, Vec<(Tier, u32)>, Option<u64>) {
+            (
+                self.active_tier,
+                self.get_token_count(),
+                self.get_tier_distribution(),
+                self.last_tier_change,
+            )
+        }
+
         /// Get current distribution of tokens across tiers
         #[ink(message)]
         pub fn get_tier_distribution(&self) -> Vec<(Tier, u32)> {
@@ -904,48 +1377,74 @@ mod registry {
         }
 
         /// Check if 80% rule should trigger tier shift
+        /// The candidate is the higher tier with the largest share of tokens.
         #[ink(message)]
         pub fn should_shift_tier(&self) -> Option<Tier> {
-            let total_tokens = self.get_token_count();
+            self.shift_target(self.best_shift_candidate())
+        }
 
-            if total_tokens < MIN_TOKENS_FOR_TIER_SHIFT {
+        /// Tier to shift to if `candidate` satisfies the tier shift policy
+        fn shift_target(&self, candidate: Option<(Tier, u32)>) -> Option<Tier> {
+            // Removed tokens leave gaps in the ID range, so use the live count
+            if self.get_token_count() < self.min_tokens_for_tier_shift {
                 return None;
             }
 
-            // Check each tier higher than current active tier
-            for check_tier in self.get_higher_tiers() {
-                let count = self.tier_distribution.get(check_tier).unwrap_or(0);
-                // Fixed: Use checked arithmetic for percentage calculation to avoid side effects
-                if let Some(percentage_times_100) = count.checked_mul(100) {
-                    if let Some(percentage) = percentage_times_100.checked_div(total_tokens) {
-                        if percentage >= TIER_SHIFT_THRESHOLD_PERCENT {
-                            return Some(check_tier);
-                        }
-                    }
-                }
-            }
-
-            None
+            candidate
+                .filter(|&(_, percentage)| percentage >= self.tier_shift_threshold_percent)
+                .map(|(tier, _)| tier)
         }
 
-        /// Execute tier shift (automatic or manual)
+        /// Manually shift the active tier (owner only)
+        /// The new tier needs at least `min_tokens_for_tier_shift` tokens at or above it;
+        /// `force` skips that check for emergencies.
         #[ink(message)]
-        pub fn shift_active_tier(&mut self, new_tier: Tier, reason: String) -> Result<(), Error> {
-            // Only owner can manually override, automatic shifts are allowed
-            if reason != "80_percent_rule" {
-                self.ensure_owner()?;
+        pub fn shift_active_tier(
+            &mut self,
+            new_tier: Tier,
+            reason: String,
+            force: bool,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if !force && self.count_tokens_at_or_above(new_tier) < self.min_tokens_for_tier_shift {
+                self.emit_operation_failed("shift_active_tier", Error::InvalidParameter);
+                return Err(Error::InvalidParameter);
             }
 
+            self.apply_active_tier_shift(new_tier, reason);
+
+            Ok(())
+        }
+
+        /// Tokens whose tier is `tier` or higher, from the distribution cache
+        fn count_tokens_at_or_above(&self, tier: Tier) -> u32 {
+            [
+                Tier::None,
+                Tier::Tier1,
+                Tier::Tier2,
+                Tier::Tier3,
+                Tier::Tier4,
+            ]
+            .into_iter()
+            .filter(|candidate| candidate.as_u32() >= tier.as_u32())
+            .fold(0u32, |total, candidate| {
+                total.saturating_add(self.tier_distribution.get(candidate).unwrap_or(0))
+            })
+        }
+
+        /// Set the active tier and emit `ActiveTierShifted` (automatic or manual)
+        fn apply_active_tier_shift(&mut self, new_tier: Tier, reason: String) {
             let old_tier = self.active_tier;
             if old_tier == new_tier {
-                return Ok(()); // No change needed
+                return; // No change needed
             }
 
             self.active_tier = new_tier;
             self.last_tier_change = Some(self.env().block_timestamp());
 
             let total_tokens = self.get_token_count();
-            let qualifying_tokens = self.tier_distribution.get(new_tier).unwrap_or(0);
+            let qualifying_tokens = self.count_tokens_at_or_above(new_tier);
 
             self.env().emit_event(ActiveTierShifted {
                 old_tier,
@@ -955,17 +1454,53 @@ mod registry {
                 tokens_qualifying: qualifying_tokens,
                 total_tokens,
             });
-
-            Ok(())
         }
 
         /// Automatic tier shift check and execution
         fn check_and_execute_auto_tier_shift(&mut self) {
-            if let Some(new_tier) = self.should_shift_tier() {
-                let _ = self.shift_active_tier(new_tier, "80_percent_rule".into());
+            let candidate = self.best_shift_candidate();
+            let shift_to = self.shift_target(candidate);
+            let (candidate_tier, candidate_percentage) = match candidate {
+                Some((tier, percentage)) => (Some(tier), percentage),
+                None => (None, 0),
+            };
+
+            self.env().emit_event(TierShiftEvaluated {
+                active_tier: self.active_tier,
+                candidate_tier,
+                candidate_percentage,
+                threshold_met: shift_to.is_some(),
+                timestamp: self.env().block_timestamp(),
+            });
+
+            if let Some(new_tier) = shift_to {
+                self.apply_active_tier_shift(new_tier, "80_percent_rule".into());
             }
         }
 
+        /// Higher tier with the largest share of tokens, as (tier, percentage)
+        /// Ties go to the lower tier.
+        fn best_shift_candidate(&self) -> Option<(Tier, u32)> {
+            let total_tokens = self.get_token_count();
+            if total_tokens == 0 {
+                return None;
+            }
+
+            let mut best: Option<(Tier, u32)> = None;
+            for check_tier in self.get_higher_tiers() {
+                let count = self.tier_distribution.get(check_tier).unwrap_or(0);
+                let percentage = count
+                    .saturating_mul(100)
+                    .checked_div(total_tokens)
+                    .unwrap_or(0);
+                if best.map_or(true, |(_, best_percentage)| percentage > best_percentage) {
+                    best = Some((check_tier, percentage));
+                }
+            }
+
+            best
+        }
+
         // ===== TIER CONFIGURATION MANAGEMENT =====
 
         /// Set DOT/USD oracle contract (owner only)
@@ -1018,6 +1553,12 @@ mod registry {
             Ok(())
         }
 
+        /// Get the USD to plancks rate used by the last tier calculation and when it was observed
+        #[ink(message)]
+        pub fn get_last_used_usd_rate(&self) -> (u128, u64) {
+            (self.last_used_usd_rate, self.last_usd_rate_timestamp)
+        }
+
         /// Get current tier thresholds
         #[ink(message)]
         pub fn get_tier_thresholds(&self) -> TierThresholds {
@@ -1030,6 +1571,17 @@ mod registry {
             self.active_tier
         }
 
+        /// Get current active tier, failing with `InvalidTier` if it is `Tier::None`
+        /// The selector is pinned as `shared::tier::GET_ACTIVE_TIER_CHECKED_SELECTOR`
+        /// for callers that build the call by hand (e.g. Staking).
+        #[ink(message, selector = 0x9B4F6231)]
+        pub fn get_active_tier_checked(&self) -> Result<Tier, Error> {
+            match self.active_tier {
+                Tier::None => Err(Error::InvalidTier),
+                tier => Ok(tier),
+            }
+        }
+
         /// Get last tier change timestamp
         #[ink(message)]
         pub fn get_last_tier_change(&self) -> Option<u64> {
@@ -1055,47 +1607,20 @@ mod registry {
         pub fn get_token_data(&self, token_id: u32) -> Result<EnrichedTokenData, Error> {
             let token_data = self.tokens.get(token_id).ok_or(Error::TokenNotFound)?;
 
-            // Use CallBuilder for cross-contract calls to deployed oracle
-            let price_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(token_data.oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_price"),
-                    ))
-                    .push_arg(token_data.token_contract),
-                )
-                .returns::<Option<u128>>()
+            // Typed calls through the shared Oracle trait
+            let price_result = Self::oracle_ref(token_data.oracle_contract)
+                .call()
+                .get_price(token_data.token_contract)
                 .try_invoke();
 
-            let market_cap_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(token_data.oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_market_cap"),
-                    ))
-                    .push_arg(token_data.token_contract),
-                )
-                .returns::<Option<u128>>()
+            let market_cap_result = Self::oracle_ref(token_data.oracle_contract)
+                .call()
+                .get_market_cap(token_data.token_contract)
                 .try_invoke();
 
-            let market_volume_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(token_data.oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_market_volume"),
-                    ))
-                    .push_arg(token_data.token_contract),
-                )
-                .returns::<Option<u128>>()
+            let market_volume_result = Self::oracle_ref(token_data.oracle_contract)
+                .call()
+                .get_market_volume(token_data.token_contract)
                 .try_invoke();
 
             // Extract values with proper error handling
@@ -1119,13 +1644,7 @@ mod registry {
                 oracle_contract: token_data.oracle_contract,
                 balance: token_data.balance,
                 weight_investment: token_data.weight_investment,
-                tier: match token_data.tier {
-                    Tier::None => 0,
-                    Tier::Tier1 => 1,
-                    Tier::Tier2 => 2,
-                    Tier::Tier3 => 3,
-                    Tier::Tier4 => 4,
-                },
+                tier: token_data.tier.as_u32(),
                 market_cap,
                 market_volume,
                 price,
@@ -1134,13 +1653,101 @@ mod registry {
             Ok(enriched_data)
         }
 
+        /// Get token data with live oracle prices for several tokens in one call
+        /// Unknown token IDs are skipped.
+        #[ink(message)]
+        pub fn get_token_data_batch(&self, token_ids: Vec<u32>) -> Vec<(u32, EnrichedTokenData)> {
+            token_ids
+                .into_iter()
+                .filter_map(|token_id| {
+                    self.get_token_data(token_id)
+                        .ok()
+                        .map(|data| (token_id, data))
+                })
+                .collect()
+        }
+
+        /// Get stored data for a page of registered tokens (no oracle calls)
+        /// Pages over active token IDs; `limit` is capped at MAX_PAGE_SIZE.
+        #[ink(message)]
+        pub fn get_all_enhanced_tokens(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(u32, EnhancedTokenData)> {
+            self.active_token_ids_page(offset, limit)
+                .iter()
+                .filter_map(|&token_id| self.tokens.get(token_id).map(|data| (token_id, data)))
+                .collect()
+        }
+
+        /// Get stored data, including tier and pending changes, for tokens with IDs in
+        /// `[start_id, start_id + limit)` (no oracle calls)
+        /// `limit` above MAX_PAGE_SIZE is rejected, so the next page always starts at
+        /// `start_id + limit`. Removed IDs are skipped and pages can be short; keep
+        /// paging until `start_id` passes `get_highest_token_id`.
+        #[ink(message)]
+        pub fn get_enhanced_tokens_by_id_range(
+            &self,
+            start_id: u32,
+            limit: u32,
+        ) -> Result<Vec<(u32, EnhancedTokenData)>, Error> {
+            if limit > MAX_PAGE_SIZE {
+                return Err(Error::InvalidParameter);
+            }
+
+            let end_id = start_id.saturating_add(limit).min(self.next_token_id);
+
+            Ok((start_id.max(1)..end_id)
+                .filter_map(|token_id| self.tokens.get(token_id).map(|data| (token_id, data)))
+                .collect())
+        }
+
+        /// Get token data with live oracle prices for a page of registered tokens
+        /// Pages over active token IDs; `limit` is capped at MAX_PAGE_SIZE.
+        #[ink(message)]
+        pub fn get_all_enriched_tokens(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(u32, EnrichedTokenData)> {
+            self.get_token_data_batch(self.active_token_ids_page(offset, limit).to_vec())
+        }
+
+        /// Check whether a token's oracle price is stale
+        /// Unreachable oracles are reported as stale.
+        #[ink(message)]
+        pub fn is_token_price_stale(&self, token_id: u32) -> Result<bool, Error> {
+            let token_data = self.tokens.get(token_id).ok_or(Error::TokenNotFound)?;
+
+            Ok(self
+                .query_price_staleness(token_data.token_contract, token_data.oracle_contract)
+                .unwrap_or(true))
+        }
+
+        /// Ask a token's oracle whether its price is stale (None if the call fails)
+        fn query_price_staleness(
+            &self,
+            token_contract: AccountId,
+            oracle_contract: AccountId,
+        ) -> Option<bool> {
+            let stale_result = Self::oracle_ref(oracle_contract)
+                .call()
+                .is_price_stale(token_contract)
+                .try_invoke();
+
+            match stale_result {
+                Ok(Ok(is_stale)) => Some(is_stale),
+                _ => None,
+            }
+        }
+
         /// Get tokens by tier
         #[ink(message)]
         pub fn get_tokens_by_tier(&self, tier: Tier) -> Vec<u32> {
             let mut tokens = Vec::new();
-            let total_tokens = self.get_token_count();
 
-            for token_id in 1..=total_tokens {
+            for &token_id in &self.active_token_ids {
                 if let Some(token_data) = self.tokens.get(token_id) {
                     if token_data.tier == tier {
                         tokens.push(token_id);
@@ -1151,13 +1758,87 @@ mod registry {
             tokens
         }
 
+        /// Get tokens of a tier among IDs in `[start_id, start_id + limit)`
+        /// `limit` above MAX_PAGE_SIZE is rejected, so the next page always starts at
+        /// `start_id + limit`. Pages can be empty or short when IDs are sparse; callers
+        /// should keep paging until `start_id` passes `get_highest_token_id`, or until
+        /// they have collected `get_tokens_by_tier_count`.
+        #[ink(message)]
+        pub fn get_tokens_by_tier_paged(
+            &self,
+            tier: Tier,
+            start_id: u32,
+            limit: u32,
+        ) -> Result<Vec<u32>, Error> {
+            if limit > MAX_PAGE_SIZE {
+                return Err(Error::InvalidParameter);
+            }
+
+            let mut tokens = Vec::new();
+            let end_id = start_id.saturating_add(limit).min(self.next_token_id);
+
+            for token_id in start_id.max(1)..end_id {
+                if let Some(token_data) = self.tokens.get(token_id) {
+                    if token_data.tier == tier {
+                        tokens.push(token_id);
+                    }
+                }
+            }
+
+            Ok(tokens)
+        }
+
+        /// Get number of tokens in a tier (from the distribution cache)
+        #[ink(message)]
+        pub fn get_tokens_by_tier_count(&self, tier: Tier) -> u32 {
+            self.tier_distribution.get(tier).unwrap_or(0)
+        }
+
+        /// Get the highest token ID ever assigned (0 if none)
+        #[ink(message)]
+        pub fn get_highest_token_id(&self) -> u32 {
+            self.next_token_id.saturating_sub(1)
+        }
+
+        /// Get the last committed tier changes for a token, oldest first
+        #[ink(message)]
+        pub fn get_tier_history(&self, token_id: u32) -> Vec<(Tier, u64)> {
+            self.tier_history.get(token_id).unwrap_or_default()
+        }
+
+        /// Get tokens whose stored tier differs from their live calculated tier
+        /// Tokens with a pending change or unavailable market data are skipped.
+        /// Returns: Vec<(token_id, current_tier, calculated_tier)>
+        #[ink(message)]
+        pub fn get_mispriced_tiers(&self) -> Vec<(u32, Tier, Tier)> {
+            let mut mispriced = Vec::new();
+
+            for &token_id in &self.active_token_ids {
+                if let Some(token_data) = self.tokens.get(token_id) {
+                    if token_data.pending_tier_change.is_some() {
+                        continue;
+                    }
+
+                    if let Some(calculated_tier) = self.calculate_token_tier_internal(
+                        token_data.token_contract,
+                        token_data.oracle_contract,
+                    ) {
+                        if calculated_tier != token_data.tier {
+                            mispriced.push((token_id, token_data.tier, calculated_tier));
+                        }
+                    }
+                }
+            }
+
+            mispriced
+        }
+
         /// Get tokens with pending tier changes
         #[ink(message)]
         pub fn get_tokens_with_pending_changes(&self) -> Vec<(u32, Tier, Tier, u64)> {
             let mut pending_tokens = Vec::new();
-            let total_tokens = self.get_token_count();
 
-            for token_id in 1..=total_tokens {
+            for &token_id in &self.active_token_ids {
                 if let Some(token_data) = self.tokens.get(token_id) {
                     if let (Some(pending_tier), Some(change_time)) = (
                         token_data.pending_tier_change,
@@ -1173,10 +1854,17 @@ mod registry {
 
         // ===== EXISTING QUERY FUNCTIONS (updated) =====
 
-        /// Get total number of registered tokens
+        /// Get total number of registered tokens (removed tokens are not counted)
+        /// IDs are never reused, so this can be lower than `get_highest_token_id`.
         #[ink(message)]
         pub fn get_token_count(&self) -> u32 {
-            self.next_token_id.saturating_sub(1)
+            self.active_token_ids.len() as u32
+        }
+
+        /// Get IDs of all registered tokens, skipping removed ones
+        #[ink(message)]
+        pub fn get_active_token_ids(&self) -> Vec<u32> {
+            self.active_token_ids.clone()
         }
 
         /// Get the owner
@@ -1201,13 +1889,7 @@ mod registry {
                 oracle_contract: enhanced_data.oracle_contract,
                 balance: enhanced_data.balance,
                 weight_investment: enhanced_data.weight_investment,
-                tier: match enhanced_data.tier {
-                    Tier::None => 0,
-                    Tier::Tier1 => 1,
-                    Tier::Tier2 => 2,
-                    Tier::Tier3 => 3,
-                    Tier::Tier4 => 4,
-                },
+                tier: enhanced_data.tier.as_u32(),
             })
         }
 
@@ -1239,16 +1921,19 @@ mod registry {
                 token_data.tier_change_timestamp = Some(current_time);
                 token_data.pending_tier_change = None;
 
+                let token_id = self
+                    .token_contract_to_id
+                    .get(token_data.token_contract)
+                    .unwrap_or(0);
+                self.record_tier_history(token_id, new_tier, current_time);
+
                 // Emit tier change event
                 if let Some((market_cap, volume)) = self.get_market_data_from_oracle(
                     token_data.token_contract,
                     token_data.oracle_contract,
                 ) {
                     self.env().emit_event(TokenTierChanged {
-                        token_id: self
-                            .token_contract_to_id
-                            .get(token_data.token_contract)
-                            .unwrap_or(0),
+                        token_id,
                         token_contract: token_data.token_contract,
                         old_tier,
                         new_tier,
@@ -1276,40 +1961,39 @@ mod registry {
             }
         }
 
-        /// Get market data from oracle (helper function)
+        /// Get market data from oracle, falling back to the token's backup oracle
         fn get_market_data_from_oracle(
             &self,
             token_contract: AccountId,
             oracle_contract: AccountId,
+        ) -> Option<(u128, u128)> {
+            self.query_market_data(token_contract, oracle_contract)
+                .or_else(|| {
+                    let backup = self
+                        .token_contract_to_id
+                        .get(token_contract)
+                        .and_then(|token_id| self.tokens.get(token_id))
+                        .and_then(|token_data| token_data.backup_oracle)?;
+                    self.query_market_data(token_contract, backup)
+                })
+        }
+
+        /// Query market cap and volume from a single oracle
+        fn query_market_data(
+            &self,
+            token_contract: AccountId,
+            oracle_contract: AccountId,
         ) -> Option<(u128, u128)> {
             // Get market cap
-            let market_cap_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_market_cap"),
-                    ))
-                    .push_arg(token_contract),
-                )
-                .returns::<Option<u128>>()
+            let market_cap_result = Self::oracle_ref(oracle_contract)
+                .call()
+                .get_market_cap(token_contract)
                 .try_invoke();
 
             // Get market volume
-            let market_volume_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_market_volume"),
-                    ))
-                    .push_arg(token_contract),
-                )
-                .returns::<Option<u128>>()
+            let market_volume_result = Self::oracle_ref(oracle_contract)
+                .call()
+                .get_market_volume(token_contract)
                 .try_invoke();
 
             // Extract values
@@ -1337,6 +2021,16 @@ mod registry {
             }
         }
 
+        /// Slice of active token IDs for a paged query
+        fn active_token_ids_page(&self, offset: u32, limit: u32) -> &[u32] {
+            let len = self.active_token_ids.len();
+            let start = (offset as usize).min(len);
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE) as usize)
+                .min(len);
+            &self.active_token_ids[start..end]
+        }
+
         /// Increment tier count in distribution cache
         fn increment_tier_count(&mut self, tier: Tier) {
             let current_count = self.tier_distribution.get(tier).unwrap_or(0);
@@ -1344,6 +2038,17 @@ mod registry {
                 .insert(tier, &(current_count.saturating_add(1)));
         }
 
+        /// Append a committed tier change, dropping the oldest entries beyond the cap
+        fn record_tier_history(&mut self, token_id: u32, tier: Tier, timestamp: u64) {
+            let mut history = self.tier_history.get(token_id).unwrap_or_default();
+            history.push((tier, timestamp));
+            if history.len() > MAX_TIER_HISTORY {
+                let excess = history.len().saturating_sub(MAX_TIER_HISTORY);
+                history.drain(..excess);
+            }
+            self.tier_history.insert(token_id, &history);
+        }
+
         /// Decrement tier count in distribution cache
         fn decrement_tier_count(&mut self, tier: Tier) {
             let current_count = self.tier_distribution.get(tier).unwrap_or(0);
@@ -1378,18 +2083,9 @@ mod registry {
             // Get DOT price in USD from oracle (assuming DOT is represented by a special address)
             let dot_token_address = AccountId::from([0xFF; 32]); // Special address for DOT itself
 
-            let dot_price_result = ink::env::call::build_call::<ink::env::DefaultEnvironment>()
-                .call(oracle_contract)
-                .call_v1()
-                .gas_limit(0)
-                .transferred_value(0)
-                .exec_input(
-                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
-                        ink::selector_bytes!("get_price"),
-                    ))
-                    .push_arg(dot_token_address),
-                )
-                .returns::<Option<u128>>()
+            let dot_price_result = Self::oracle_ref(oracle_contract)
+                .call()
+                .get_price(dot_token_address)
                 .try_invoke();
 
             match dot_price_result {
@@ -1415,6 +2111,11 @@ mod registry {
             }
         }
 
+        /// Typed reference to an oracle implementing `shared::Oracle`
+        fn oracle_ref(oracle_contract: AccountId) -> ink::contract_ref!(shared::Oracle) {
+            oracle_contract.into()
+        }
+
         /// Emit operation failed event for monitoring
         fn emit_operation_failed(&self, operation: &str, error: Error) {
             self.env().emit_event(OperationFailed {
@@ -1424,4 +2125,321 @@ mod registry {
             });
         }
     }
+
+    impl shared::Registry for Registry {
+        /// Get token data with live oracle prices
+        #[ink(message)]
+        fn get_token_data(&self, token_id: u32) -> Result<EnrichedTokenData, Error> {
+            Registry::get_token_data(self, token_id)
+        }
+
+        /// Get the currently active tier as u32
+        #[ink(message)]
+        fn get_active_tier(&self) -> u32 {
+            Registry::get_active_tier(self).as_u32()
+        }
+
+        /// Get the active token IDs classified in a tier; empty for unknown tiers
+        #[ink(message)]
+        fn get_tokens_by_tier(&self, tier: u32) -> Vec<u32> {
+            match Tier::from_u32(tier) {
+                Some(tier) => Registry::get_tokens_by_tier(self, tier),
+                None => Vec::new(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn assert_implements_registry<T: shared::Registry>() {}
+
+        #[test]
+        fn registry_implements_shared_trait() {
+            assert_implements_registry::<Registry>();
+        }
+
+        #[test]
+        fn active_tier_checked_selector_matches_shared_constant() {
+            assert_eq!(
+                u32::from_be_bytes(shared::tier::GET_ACTIVE_TIER_CHECKED_SELECTOR),
+                0x9B4F6231
+            );
+        }
+
+        #[ink::test]
+        fn tier_shift_policy_is_configurable() {
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+            for byte in 1..=4u8 {
+                registry.store_token(AccountId::from([byte; 32]), oracle, Tier::Tier2);
+            }
+
+            // Below the default minimum of 5 tokens
+            assert_eq!(registry.should_shift_tier(), None);
+            assert_eq!(registry.set_min_tokens_for_shift(4), Ok(()));
+            assert_eq!(registry.should_shift_tier(), Some(Tier::Tier2));
+
+            assert_eq!(
+                registry.set_tier_shift_threshold(0),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                registry.set_tier_shift_threshold(101),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(registry.set_tier_shift_threshold(100), Ok(()));
+            assert_eq!(registry.get_tier_shift_policy(), (100, 4));
+        }
+
+        #[ink::test]
+        fn manual_tier_shift_requires_qualifying_tokens() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+            for byte in 1..=5u8 {
+                let tier = if byte <= 4 { Tier::Tier3 } else { Tier::Tier4 };
+                registry.store_token(AccountId::from([byte; 32]), oracle, tier);
+            }
+
+            // Only one token at or above Tier4
+            assert_eq!(
+                registry.shift_active_tier(Tier::Tier4, "manual_override".into(), false),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(registry.get_active_tier(), Tier::Tier1);
+
+            // Tier3 counts the Tier4 token too
+            assert_eq!(
+                registry.shift_active_tier(Tier::Tier3, "manual_override".into(), false),
+                Ok(())
+            );
+            assert_eq!(
+                registry.shift_active_tier(Tier::Tier4, "emergency".into(), true),
+                Ok(())
+            );
+            assert_eq!(registry.get_active_tier(), Tier::Tier4);
+
+            // The automatic reason no longer bypasses the owner check
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                registry.shift_active_tier(Tier::Tier1, "80_percent_rule".into(), true),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn index_summary_counts_unranked_tokens() {
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+            registry.store_token(AccountId::from([1; 32]), oracle, Tier::Tier2);
+            registry.store_token(AccountId::from([2; 32]), oracle, Tier::None);
+
+            let (active_tier, total_tokens, distribution, last_tier_change) =
+                registry.get_index_summary();
+            assert_eq!(active_tier, Tier::Tier1);
+            assert_eq!(total_tokens, 2);
+            assert_eq!(
+                distribution,
+                vec![
+                    (Tier::None, 1),
+                    (Tier::Tier1, 0),
+                    (Tier::Tier2, 1),
+                    (Tier::Tier3, 0),
+                    (Tier::Tier4, 0),
+                ]
+            );
+            assert_eq!(last_tier_change, None);
+        }
+
+        #[ink::test]
+        fn tokens_by_tier_paged_rejects_oversized_pages() {
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+            for byte in 1..=4u8 {
+                let tier = if byte % 2 == 0 {
+                    Tier::Tier2
+                } else {
+                    Tier::Tier1
+                };
+                registry.store_token(AccountId::from([byte; 32]), oracle, tier);
+            }
+
+            assert_eq!(
+                registry.get_tokens_by_tier_paged(Tier::Tier2, 1, MAX_PAGE_SIZE + 1),
+                Err(Error::InvalidParameter)
+            );
+            assert_eq!(
+                registry.get_tokens_by_tier_paged(Tier::Tier2, 1, 2),
+                Ok(vec![2])
+            );
+            assert_eq!(
+                registry.get_tokens_by_tier_paged(Tier::Tier2, 3, 2),
+                Ok(vec![4])
+            );
+            assert_eq!(
+                registry.get_tokens_by_tier_paged(Tier::Tier2, 5, 2),
+                Ok(vec![])
+            );
+        }
+
+        #[ink::test]
+        fn enhanced_tokens_by_id_range_skips_removed_ids() {
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+            for byte in 1..=3u8 {
+                registry.store_token(AccountId::from([byte; 32]), oracle, Tier::Tier1);
+            }
+            registry.remove_token(2).unwrap();
+
+            let ids = |page: Vec<(u32, EnhancedTokenData)>| -> Vec<u32> {
+                page.into_iter().map(|(token_id, _)| token_id).collect()
+            };
+            assert_eq!(
+                registry.get_enhanced_tokens_by_id_range(1, 2).map(ids),
+                Ok(vec![1])
+            );
+            assert_eq!(
+                registry.get_enhanced_tokens_by_id_range(3, 2).map(ids),
+                Ok(vec![3])
+            );
+            assert_eq!(
+                registry.get_enhanced_tokens_by_id_range(1, MAX_PAGE_SIZE + 1),
+                Err(Error::InvalidParameter)
+            );
+            // Offset paging walks the active list without gaps
+            assert_eq!(ids(registry.get_all_enhanced_tokens(0, 2)), vec![1, 3]);
+        }
+
+        #[ink::test]
+        fn active_tier_checked_rejects_none() {
+            let mut registry = Registry::new();
+            assert_eq!(registry.get_active_tier_checked(), Ok(Tier::Tier1));
+
+            registry.active_tier = Tier::None;
+            assert_eq!(registry.get_active_tier_checked(), Err(Error::InvalidTier));
+        }
+
+        #[ink::test]
+        fn tier_shift_percentage_ignores_removed_tokens() {
+            let mut registry = Registry::new();
+            let oracle = AccountId::from([0xFF; 32]);
+
+            // 6 of 10 tokens qualify for Tier2: below the 80% threshold
+            for byte in 1..=10u8 {
+                let tier = if byte <= 6 { Tier::Tier2 } else { Tier::None };
+                registry.store_token(AccountId::from([byte; 32]), oracle, tier);
+            }
+            assert_eq!(registry.best_shift_candidate(), Some((Tier::Tier2, 60)));
+            assert_eq!(registry.should_shift_tier(), None);
+
+            for token_id in [7, 8] {
+                registry.remove_token(token_id).unwrap();
+            }
+            assert_eq!(registry.get_token_count(), 8);
+            assert_eq!(registry.get_highest_token_id(), 10);
+            assert_eq!(registry.best_shift_candidate(), Some((Tier::Tier2, 75)));
+            assert_eq!(registry.get_active_tier(), Tier::Tier1);
+
+            // 6 of 7 live tokens (85%) qualify, so the removal triggers the shift
+            registry.remove_token(9).unwrap();
+            assert_eq!(registry.get_token_count(), 7);
+            assert_eq!(registry.get_active_token_ids(), vec![1, 2, 3, 4, 5, 6, 10]);
+            assert_eq!(registry.get_active_tier(), Tier::Tier2);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Calls the deployed Registry the way Staking does, by the shared raw selector
+        #[ink_e2e::test]
+        async fn active_tier_checked_resolves_by_shared_selector<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = RegistryRef::new();
+            let registry = client
+                .instantiate("registry", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("registry instantiate failed");
+
+            let call = build_call::<ink::env::DefaultEnvironment>()
+                .call(registry.account_id)
+                .exec_input(ExecutionInput::new(Selector::new(
+                    shared::tier::GET_ACTIVE_TIER_CHECKED_SELECTOR,
+                )))
+                .returns::<Result<Tier, Error>>();
+            let result = client.call(&ink_e2e::alice(), &call).dry_run().await?;
+
+            assert_eq!(result.return_value(), Ok(Tier::Tier1));
+
+            Ok(())
+        }
+
+        /// The portfolio's batched valuation path must see the same data as per-token calls
+        #[ink_e2e::test]
+        async fn token_data_batch_matches_per_token_calls<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = RegistryRef::new();
+            let registry = client
+                .instantiate("registry", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("registry instantiate failed");
+            let mut call_builder = registry.call_builder::<Registry>();
+
+            let oracle = AccountId::from([0xFF; 32]);
+            let entries = vec![
+                (AccountId::from([1; 32]), oracle),
+                (AccountId::from([2; 32]), oracle),
+                (AccountId::from([3; 32]), oracle),
+            ];
+            client
+                .call(&ink_e2e::alice(), &call_builder.add_tokens_batch(entries))
+                .submit()
+                .await
+                .expect("add_tokens_batch failed");
+            client
+                .call(&ink_e2e::alice(), &call_builder.remove_token(2))
+                .submit()
+                .await
+                .expect("remove_token failed");
+
+            let token_ids = vec![1, 2, 3, 99];
+            let batch = client
+                .call(
+                    &ink_e2e::alice(),
+                    &call_builder.get_token_data_batch(token_ids.clone()),
+                )
+                .dry_run()
+                .await?
+                .return_value();
+
+            let mut per_token = Vec::new();
+            for token_id in token_ids {
+                let result = client
+                    .call(&ink_e2e::alice(), &call_builder.get_token_data(token_id))
+                    .dry_run()
+                    .await?
+                    .return_value();
+                if let Ok(data) = result {
+                    per_token.push((token_id, data));
+                }
+            }
+
+            // Removed and unknown IDs are skipped by both paths
+            assert_eq!(batch.len(), 2);
+            assert_eq!(batch, per_token);
+
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,171 @@
+// w3pi/contracts/shared/src/errors.rs
+
+use scale::{Decode, Encode};
+
+/// Enhanced error types for better debugging and validation
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    // Authorization errors
+    Unauthorized,
+    UnauthorizedRole,
+
+    // Token management errors
+    TokenNotFound,
+    TokenAlreadyExists,
+    InvalidTokenContract,
+    ZeroAddress,
+
+    // Parameter validation errors
+    InvalidWeight, // Weight not in 0-10000 range
+    InvalidTier,   // Tier not in 0-5 range
+    InvalidParameter,
+
+    // Oracle and external errors
+    OracleCallFailed,
+
+    // Business logic errors
+    InsufficientBalance,
+    SlippageExceeded,   // Swap output below the caller's minimum
+    PriceImpactTooHigh, // Swap moves a pool further than the caller allows
+
+    // Appended variants keep earlier SCALE discriminants stable
+    ContractPaused,          // Contract or operation is paused
+    ReentrantCall,           // Reentrancy guard already held
+    WeightExceeded,          // Aggregate weight above 10000 basis points
+    PriceStale,              // Price older than the staleness threshold
+    PoolNotFound,            // No DEX pool for the token pair
+    CrossContractCallFailed, // Callee trapped or could not be decoded
+    TransferFailed,          // Token transfer rejected
+    AmountZero,              // Amount must be non-zero
+    MaxTokensReached,        // Holding limit reached
+    ArithmeticOverflow,      // Checked arithmetic overflowed or underflowed
+    InsufficientAllowance,   // Token allowance below the requested amount
+}
+
+impl Error {
+    /// Stable numeric code for the variant, grouped by category
+    /// Codes are independent of the SCALE discriminant and must never be reused.
+    pub fn error_code(&self) -> u32 {
+        match self {
+            Error::Unauthorized => 100,
+            Error::UnauthorizedRole => 101,
+            Error::ContractPaused => 102,
+            Error::ReentrantCall => 103,
+            Error::TokenNotFound => 200,
+            Error::TokenAlreadyExists => 201,
+            Error::InvalidTokenContract => 202,
+            Error::ZeroAddress => 203,
+            Error::PoolNotFound => 204,
+            Error::InvalidWeight => 300,
+            Error::InvalidTier => 301,
+            Error::InvalidParameter => 302,
+            Error::WeightExceeded => 303,
+            Error::AmountZero => 304,
+            Error::MaxTokensReached => 305,
+            Error::ArithmeticOverflow => 306,
+            Error::OracleCallFailed => 400,
+            Error::PriceStale => 401,
+            Error::CrossContractCallFailed => 402,
+            Error::InsufficientBalance => 500,
+            Error::SlippageExceeded => 501,
+            Error::PriceImpactTooHigh => 502,
+            Error::TransferFailed => 503,
+            Error::InsufficientAllowance => 504,
+        }
+    }
+
+    /// Human-readable description of the error
+    pub fn error_message(&self) -> &'static str {
+        match self {
+            Error::Unauthorized => "Caller is not authorized",
+            Error::UnauthorizedRole => "Caller lacks the required role",
+            Error::ContractPaused => "Contract is paused",
+            Error::ReentrantCall => "Reentrant call rejected",
+            Error::TokenNotFound => "Token not found",
+            Error::TokenAlreadyExists => "Token already exists",
+            Error::InvalidTokenContract => "Invalid token contract",
+            Error::ZeroAddress => "Zero address not allowed",
+            Error::PoolNotFound => "Pool not found",
+            Error::InvalidWeight => "Weight must be between 0 and 10000",
+            Error::InvalidTier => "Tier must be between 0 and 5",
+            Error::InvalidParameter => "Invalid parameter",
+            Error::WeightExceeded => "Total weight exceeds 10000",
+            Error::AmountZero => "Amount cannot be zero",
+            Error::MaxTokensReached => "Maximum tokens limit reached",
+            Error::ArithmeticOverflow => "Arithmetic overflow",
+            Error::OracleCallFailed => "Oracle call failed",
+            Error::PriceStale => "Price is stale",
+            Error::CrossContractCallFailed => "Cross-contract call failed",
+            Error::InsufficientBalance => "Insufficient balance",
+            Error::SlippageExceeded => "Swap output below minimum",
+            Error::PriceImpactTooHigh => "Swap price impact too high",
+            Error::TransferFailed => "Token transfer failed",
+            Error::InsufficientAllowance => "Insufficient allowance",
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "E{}: {}", self.error_code(), self.error_message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_ERRORS: [Error; 24] = [
+        Error::Unauthorized,
+        Error::UnauthorizedRole,
+        Error::TokenNotFound,
+        Error::TokenAlreadyExists,
+        Error::InvalidTokenContract,
+        Error::ZeroAddress,
+        Error::InvalidWeight,
+        Error::InvalidTier,
+        Error::InvalidParameter,
+        Error::OracleCallFailed,
+        Error::InsufficientBalance,
+        Error::SlippageExceeded,
+        Error::PriceImpactTooHigh,
+        Error::ContractPaused,
+        Error::ReentrantCall,
+        Error::WeightExceeded,
+        Error::PriceStale,
+        Error::PoolNotFound,
+        Error::CrossContractCallFailed,
+        Error::TransferFailed,
+        Error::AmountZero,
+        Error::MaxTokensReached,
+        Error::ArithmeticOverflow,
+        Error::InsufficientAllowance,
+    ];
+
+    #[test]
+    fn error_codes_are_stable_and_unique() {
+        assert_eq!(Error::Unauthorized.error_code(), 100);
+        assert_eq!(Error::TokenNotFound.error_code(), 200);
+        assert_eq!(Error::InvalidParameter.error_code(), 302);
+        assert_eq!(Error::OracleCallFailed.error_code(), 400);
+        assert_eq!(Error::SlippageExceeded.error_code(), 501);
+
+        let mut codes: Vec<u32> = ALL_ERRORS.iter().map(Error::error_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), ALL_ERRORS.len());
+    }
+
+    #[test]
+    fn display_includes_code_and_message() {
+        assert_eq!(Error::TokenNotFound.to_string(), "E200: Token not found");
+    }
+
+    #[test]
+    fn scale_encoding_is_unchanged() {
+        assert_eq!(Error::Unauthorized.encode(), vec![0]);
+        assert_eq!(Error::InsufficientBalance.encode(), vec![10]);
+        assert_eq!(Error::ContractPaused.encode(), vec![13]);
+    }
+}
@@ -2,9 +2,17 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use ink::prelude::vec::Vec;
 use ink::primitives::AccountId;
 pub use scale::{Decode, Encode};
 
+pub mod errors;
+pub mod psp22;
+pub mod tier;
+pub mod utils;
+
+pub use errors::Error;
+
 /// Token data structure shared between contracts
 #[derive(Decode, Encode, Clone, Debug, PartialEq)]
 #[cfg_attr(
@@ -41,32 +49,6 @@ pub struct EnrichedTokenData {
     pub price: u128,
 }
 
-/// Enhanced error types for better debugging and validation
-#[derive(Debug, PartialEq, Eq, Encode, Decode)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub enum Error {
-    // Authorization errors
-    Unauthorized,
-    UnauthorizedRole,
-
-    // Token management errors
-    TokenNotFound,
-    TokenAlreadyExists,
-    InvalidTokenContract,
-    ZeroAddress,
-
-    // Parameter validation errors
-    InvalidWeight, // Weight not in 0-10000 range
-    InvalidTier,   // Tier not in 0-5 range
-    InvalidParameter,
-
-    // Oracle and external errors
-    OracleCallFailed,
-
-    // Business logic errors
-    InsufficientBalance,
-}
-
 /// Role-based access control roles
 #[derive(Debug, PartialEq, Eq, Encode, Decode, Clone, Copy)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -90,4 +72,49 @@ pub trait Oracle {
     /// Get the market volume of a token in plancks
     #[ink(message)]
     fn get_market_volume(&self, token: AccountId) -> Option<u128>;
+
+    /// Check whether a token's price is older than its staleness threshold
+    #[ink(message)]
+    fn is_price_stale(&self, token: AccountId) -> bool;
+
+    /// Get the DOT/USD price
+    #[ink(message)]
+    fn get_dot_usd_price(&self) -> Option<u128>;
+
+    /// Check whether the DOT/USD price is stale
+    #[ink(message)]
+    fn is_dot_price_stale(&self) -> bool;
+}
+
+/// Registry trait for type-safe cross-contract calls
+/// Tiers are passed as u32 (0 = None, 1-4 = Tier1-Tier4).
+#[ink::trait_definition]
+pub trait Registry {
+    /// Get token data with live oracle prices
+    #[ink(message)]
+    fn get_token_data(&self, token_id: u32) -> Result<EnrichedTokenData, Error>;
+
+    /// Get the currently active tier
+    #[ink(message)]
+    fn get_active_tier(&self) -> u32;
+
+    /// Get the active token IDs classified in a tier
+    #[ink(message)]
+    fn get_tokens_by_tier(&self, tier: u32) -> Vec<u32>;
+}
+
+/// Portfolio trait for type-safe cross-contract calls
+#[ink::trait_definition]
+pub trait Portfolio {
+    /// Get net asset value per whole W3PI share in plancks
+    #[ink(message)]
+    fn get_nav_per_share(&self) -> Result<u128, Error>;
+
+    /// Get the last computed index value
+    #[ink(message)]
+    fn get_current_index_value(&self) -> u128;
+
+    /// Check whether the portfolio is emergency paused
+    #[ink(message)]
+    fn is_emergency_paused(&self) -> bool;
 }
@@ -0,0 +1,58 @@
+// w3pi/contracts/shared/src/psp22.rs
+
+use ink::prelude::string::String;
+use scale::{Decode, Encode};
+
+use crate::errors::Error;
+
+/// Error returned by PSP22 token messages
+/// Mirrors `PSP22Error` in the token contract variant for variant so callers can
+/// decode token results without depending on the token crate.
+#[derive(Debug, PartialEq, Eq, Encode, Decode, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+impl From<PSP22Error> for Error {
+    fn from(error: PSP22Error) -> Self {
+        match error {
+            PSP22Error::InsufficientBalance => Error::InsufficientBalance,
+            PSP22Error::InsufficientAllowance => Error::InsufficientAllowance,
+            PSP22Error::ZeroRecipientAddress | PSP22Error::ZeroSenderAddress => Error::ZeroAddress,
+            PSP22Error::Custom(_) | PSP22Error::SafeTransferCheckFailed(_) => Error::TransferFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_matches_token_contract() {
+        assert_eq!(PSP22Error::InsufficientBalance.encode(), vec![1]);
+        assert_eq!(PSP22Error::InsufficientAllowance.encode(), vec![2]);
+    }
+
+    #[test]
+    fn allowance_and_balance_errors_stay_distinct() {
+        assert_eq!(
+            Error::from(PSP22Error::InsufficientBalance),
+            Error::InsufficientBalance
+        );
+        assert_eq!(
+            Error::from(PSP22Error::InsufficientAllowance),
+            Error::InsufficientAllowance
+        );
+        assert_eq!(
+            Error::from(PSP22Error::Custom(String::from("frozen"))),
+            Error::TransferFailed
+        );
+    }
+}
@@ -0,0 +1,66 @@
+// w3pi/contracts/shared/src/tier.rs
+
+use scale::{Decode, Encode};
+
+/// Selector of the Registry's `get_active_tier_checked` message.
+/// Pinned in the Registry with `selector = 0x9B4F6231` so callers can rely on it.
+pub const GET_ACTIVE_TIER_CHECKED_SELECTOR: [u8; 4] = [0x9B, 0x4F, 0x62, 0x31];
+
+/// Enhanced tier classification for tokens
+#[derive(Debug, PartialEq, Eq, Encode, Decode, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Tier {
+    #[default]
+    None, // Below minimum thresholds
+    Tier1, // $50M market cap + $5M volume
+    Tier2, // $250M market cap + $25M volume
+    Tier3, // $500M market cap + $50M volume
+    Tier4, // $2B market cap + $200M volume
+}
+
+impl Tier {
+    /// Numeric tier used across contract boundaries (0 = None, 1-4 = Tier1-Tier4)
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Tier::None => 0,
+            Tier::Tier1 => 1,
+            Tier::Tier2 => 2,
+            Tier::Tier3 => 3,
+            Tier::Tier4 => 4,
+        }
+    }
+
+    /// Inverse of `as_u32`; None for values above 4
+    pub fn from_u32(value: u32) -> Option<Tier> {
+        match value {
+            0 => Some(Tier::None),
+            1 => Some(Tier::Tier1),
+            2 => Some(Tier::Tier2),
+            3 => Some(Tier::Tier3),
+            4 => Some(Tier::Tier4),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tier_u32_round_trips() {
+        for tier in [
+            Tier::None,
+            Tier::Tier1,
+            Tier::Tier2,
+            Tier::Tier3,
+            Tier::Tier4,
+        ] {
+            assert_eq!(Tier::from_u32(tier.as_u32()), Some(tier));
+        }
+        assert_eq!(Tier::from_u32(5), None);
+    }
+}
@@ -0,0 +1,3 @@
+// w3pi/contracts/shared/src/utils/mod.rs
+
+pub mod reentrancy_guard;
@@ -0,0 +1,93 @@
+// w3pi/contracts/shared/src/utils/reentrancy_guard.rs
+
+use crate::errors::Error;
+use scale::{Decode, Encode};
+
+/// Lock flag stored in a contract to reject reentrant message calls
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReentrancyGuard {
+    locked: bool,
+}
+
+impl ReentrancyGuard {
+    pub fn new() -> Self {
+        Self { locked: false }
+    }
+
+    /// Take the lock, failing with ReentrantCall if it is already held
+    pub fn enter(&mut self) -> Result<(), Error> {
+        if self.locked {
+            return Err(Error::ReentrantCall);
+        }
+        self.locked = true;
+        Ok(())
+    }
+
+    /// Release the lock
+    pub fn exit(&mut self) {
+        self.locked = false;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+/// Run a message body while holding `self.reentrancy_guard`
+/// The body runs in a closure so early returns and `?` still release the lock.
+#[macro_export]
+macro_rules! non_reentrant {
+    ($self:ident, $body:block) => {{
+        $self.reentrancy_guard.enter()?;
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| $body)();
+        $self.reentrancy_guard.exit();
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Guarded {
+        reentrancy_guard: ReentrancyGuard,
+    }
+
+    impl Guarded {
+        fn run(&mut self, fail: bool) -> Result<u32, Error> {
+            non_reentrant!(self, {
+                if fail {
+                    return Err(Error::InvalidParameter);
+                }
+                Ok(1)
+            })
+        }
+    }
+
+    #[test]
+    fn rejects_reentry_while_locked() {
+        let mut guard = ReentrancyGuard::new();
+        assert_eq!(guard.enter(), Ok(()));
+        assert_eq!(guard.enter(), Err(Error::ReentrantCall));
+        guard.exit();
+        assert!(!guard.is_locked());
+    }
+
+    #[test]
+    fn macro_releases_lock_on_early_return() {
+        let mut guarded = Guarded {
+            reentrancy_guard: ReentrancyGuard::new(),
+        };
+        assert_eq!(guarded.run(true), Err(Error::InvalidParameter));
+        assert!(!guarded.reentrancy_guard.is_locked());
+        assert_eq!(guarded.run(false), Ok(1));
+
+        guarded.reentrancy_guard.enter().unwrap();
+        assert_eq!(guarded.run(false), Err(Error::ReentrantCall));
+    }
+}
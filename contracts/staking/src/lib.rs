@@ -13,7 +13,7 @@ mod w3pi_staking {
     use ink::storage::Mapping;
     use shared::errors::Error;
     use shared::non_reentrant;
-    use shared::tier::Tier;
+    use shared::tier::{Tier, GET_ACTIVE_TIER_CHECKED_SELECTOR};
     use shared::utils::reentrancy_guard::ReentrancyGuard;
     use core::convert::TryFrom;
 
@@ -22,6 +22,15 @@ mod w3pi_staking {
     pub const REWARDS_RATE_ANNUAL: u128 = 5_000_000_000; // 5% APR (5% * 10^8)
     pub const SECONDS_PER_YEAR: u64 = 31_536_000; // 365 days in seconds
     pub const PERFORMANCE_FEE_PERCENT: u128 = 10; // Staking fee: 10% of rewards
+    pub const DEFAULT_EARLY_EXIT_PENALTY_BP: u32 = 1000; // 10% penalty for skipping the unstaking period
+    pub const MAX_EARLY_EXIT_PENALTY_BP: u32 = 3000; // 30% cap
+    pub const MAX_REWARDS_RATE_ANNUAL: u128 = 100_000_000_000; // 100% APR cap (same scale)
+
+    // Default tier-based reward rates, ascending with tier (same scale as REWARDS_RATE_ANNUAL)
+    pub const TIER1_REWARDS_RATE: u128 = 4_000_000_000; // 4% APR
+    pub const TIER2_REWARDS_RATE: u128 = 5_000_000_000; // 5% APR
+    pub const TIER3_REWARDS_RATE: u128 = 6_500_000_000; // 6.5% APR
+    pub const TIER4_REWARDS_RATE: u128 = 8_000_000_000; // 8% APR
 
     // Tier-based unstaking periods (in seconds)
     pub const TIER1_UNSTAKING_PERIOD: u64 = 14 * 24 * 60 * 60; // 14 days
@@ -87,6 +96,42 @@ mod w3pi_staking {
         pub fee_amount: u128,
     }
 
+    /// Event emitted when an account changes its compounding preference
+    #[ink(event)]
+    pub struct AutoCompoundUpdated {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub enabled: bool,
+    }
+
+    /// Event emitted when tokens are unstaked immediately with a penalty
+    #[ink(event)]
+    pub struct EmergencyUnstaked {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub gross_amount: u128,
+        pub penalty: u128,
+        pub net_amount: u128,
+    }
+
+    /// Event emitted when the reward pool is funded
+    #[ink(event)]
+    pub struct RewardsFunded {
+        #[ink(topic)]
+        pub funder: AccountId,
+        pub amount: u128,
+        pub reward_pool: u128,
+    }
+
+    /// Event emitted when a tier's reward rate changes
+    #[ink(event)]
+    pub struct TierAprUpdated {
+        #[ink(topic)]
+        pub tier: Tier,
+        pub old_rate: u128,
+        pub new_rate: u128,
+    }
+
     /// Main stake information structure
     #[derive(Debug, scale::Encode, scale::Decode, Clone)]
     #[cfg_attr(
@@ -102,7 +147,7 @@ mod w3pi_staking {
         pub last_claim: u64,
         /// Unstaking period for this stake (in seconds)
         pub unstaking_period: u64,
-        /// Current tier when staked (for reference)
+        /// Current tier when staked (determines the reward rate)
         pub tier_at_stake: Tier,
     }
 
@@ -129,12 +174,31 @@ mod w3pi_staking {
         fee_wallet: AccountId,
         /// Total collected fees
         total_collected_fees: u128,
+        /// Annual reward rate per tier
+        tier_apr: Mapping<Tier, u128>,
+        /// Tokens set aside for rewards (separate from staked principal)
+        reward_pool: u128,
+        /// Penalty on emergency unstakes, in basis points
+        early_exit_penalty_bp: u32,
+        /// Per-account reward compounding preference (absent = enabled)
+        auto_compound: Mapping<AccountId, bool>,
+        /// Accounts with an active stake (for aggregate views)
+        stakers: Vec<AccountId>,
+        /// Number of accounts with an active stake
+        staker_count: u32,
     }
 
     impl W3piStaking {
         /// Constructor that initializes the staking contract
         #[ink(constructor)]
         pub fn new(w3pi_token: AccountId, registry: AccountId, fee_wallet: AccountId) -> Self {
+            let mut tier_apr = Mapping::default();
+            tier_apr.insert(Tier::None, &REWARDS_RATE_ANNUAL);
+            tier_apr.insert(Tier::Tier1, &TIER1_REWARDS_RATE);
+            tier_apr.insert(Tier::Tier2, &TIER2_REWARDS_RATE);
+            tier_apr.insert(Tier::Tier3, &TIER3_REWARDS_RATE);
+            tier_apr.insert(Tier::Tier4, &TIER4_REWARDS_RATE);
+
             Self {
                 w3pi_token,
                 registry,
@@ -146,6 +210,12 @@ mod w3pi_staking {
                 reentrancy_guard: ReentrancyGuard::new(),
                 fee_wallet,
                 total_collected_fees: 0,
+                tier_apr,
+                reward_pool: 0,
+                early_exit_penalty_bp: DEFAULT_EARLY_EXIT_PENALTY_BP,
+                auto_compound: Mapping::default(),
+                stakers: Vec::new(),
+                staker_count: 0,
             }
         }
 
@@ -187,18 +257,17 @@ mod w3pi_staking {
             use ink::env::call::{build_call, ExecutionInput, Selector};
             use ink::env::DefaultEnvironment;
 
-            // Call the registry contract to get current tier
+            // Call the registry's get_active_tier_checked (selector pinned in shared)
             match build_call::<DefaultEnvironment>()
                 .call(self.registry)
-                .exec_input(ExecutionInput::new(Selector::new([0x9B, 0x4F, 0x62, 0x31]))) // get_current_tier selector
-                .returns::<Tier>()
+                .exec_input(ExecutionInput::new(Selector::new(
+                    GET_ACTIVE_TIER_CHECKED_SELECTOR,
+                )))
+                .returns::<Result<Tier, Error>>()
                 .try_invoke()
             {
-                Ok(result) => match result {
-                    Ok(tier) => Ok(tier),
-                    Err(_) => Err(Error::CrossContractCallFailed),
-                },
-                Err(_) => Err(Error::CrossContractCallFailed),
+                Ok(Ok(result)) => result,
+                _ => Err(Error::CrossContractCallFailed),
             }
         }
 
@@ -221,7 +290,7 @@ mod w3pi_staking {
             // Calculate reward: amount * rate * time_elapsed / seconds_per_year / 10^8
             stake
                 .amount
-                .saturating_mul(REWARDS_RATE_ANNUAL)
+                .saturating_mul(self.get_stake_reward_rate(stake))
                 .saturating_mul(time_elapsed_u128)
                 .checked_div(seconds_per_year_u128)
                 .unwrap_or(0)
@@ -250,6 +319,85 @@ mod w3pi_staking {
                 .map_err(|_| Error::TransferFailed)? // Handle contract error
         }
 
+        /// Track a newly created stake
+        fn add_staker(&mut self, account: AccountId) {
+            if !self.stakers.contains(&account) {
+                self.stakers.push(account);
+                self.staker_count = self.staker_count.saturating_add(1);
+            }
+        }
+
+        /// Stop tracking a fully removed stake
+        fn remove_staker(&mut self, account: AccountId) {
+            if let Some(pos) = self.stakers.iter().position(|&a| a == account) {
+                self.stakers.swap_remove(pos);
+                self.staker_count = self.staker_count.saturating_sub(1);
+            }
+        }
+
+        /// Store an account's unstaking requests, clearing the entry when empty
+        fn store_unstaking_requests(
+            &mut self,
+            account: AccountId,
+            requests: Vec<UnstakingRequest>,
+        ) {
+            if requests.is_empty() {
+                self.unstaking_requests.remove(account);
+            } else {
+                self.unstaking_requests.insert(account, &requests);
+            }
+        }
+
+        /// Pay `amount` of rewards (net + fee) out of the reward pool
+        fn consume_reward_pool(&mut self, amount: u128) -> Result<(), Error> {
+            if amount > self.reward_pool {
+                return Err(Error::InsufficientBalance);
+            }
+            self.reward_pool = self.reward_pool.saturating_sub(amount);
+            Ok(())
+        }
+
+        /// Pay out claimed rewards and fee, moving `last_claim` to `new_last_claim`
+        fn pay_rewards(
+            &mut self,
+            caller: AccountId,
+            mut stake_info: StakeInfo,
+            net_reward: u128,
+            fee_amount: u128,
+            new_last_claim: u64,
+        ) -> Result<(), Error> {
+            self.consume_reward_pool(net_reward.saturating_add(fee_amount))?;
+
+            // Update last claim time
+            stake_info.last_claim = new_last_claim;
+            self.stakes.insert(caller, &stake_info);
+
+            // Update total fees collected
+            self.total_collected_fees = self.total_collected_fees.saturating_add(fee_amount);
+
+            // Transfer net rewards to user
+            self.transfer_tokens_from_contract(caller, net_reward)?;
+
+            // Transfer fee to fee wallet (if fee is non-zero)
+            if fee_amount > 0 {
+                self.transfer_tokens_from_contract(self.fee_wallet, fee_amount)?;
+
+                // Emit fee event
+                self.env().emit_event(PerformanceFeeClaimed {
+                    account: caller,
+                    fee_amount,
+                });
+            }
+
+            // Emit reward event
+            self.env().emit_event(RewardsClaimed {
+                account: caller,
+                amount: net_reward,
+            });
+
+            Ok(())
+        }
+
         /// Calculate rewards with performance fee
         /// Returns (net_reward, fee_amount)
         fn calculate_rewards_with_fee(&self, stake: &StakeInfo) -> (u128, u128) {
@@ -270,7 +418,7 @@ mod w3pi_staking {
             // Calculate total reward: amount * rate * time_elapsed / seconds_per_year / 10^8
             let total_reward = stake
                 .amount
-                .saturating_mul(REWARDS_RATE_ANNUAL)
+                .saturating_mul(self.get_stake_reward_rate(stake))
                 .saturating_mul(time_elapsed_u128)
                 .checked_div(seconds_per_year_u128)
                 .unwrap_or(0)
@@ -319,17 +467,28 @@ mod w3pi_staking {
                 let current_time = self.env().block_timestamp();
 
                 if amount == 0 {
-                    return Err(Error::InvalidParameters);
+                    return Err(Error::InvalidParameter);
                 }
 
                 // Get unstaking period based on current tier
                 let unstaking_period = self.get_unstaking_period()?;
                 let current_tier = self.get_current_tier()?;
 
+                // Rewards compounded into principal (taken from the reward pool)
+                let mut compounded: u128 = 0;
+                // Rewards paid out instead when the user opted out of compounding
+                let mut paid_out: u128 = 0;
+
                 // Check if user already has a stake
                 let stake_info = if let Some(existing_stake) = self.stakes.get(caller) {
                     // Calculate pending rewards and fee
                     let (net_reward, fee_amount) = self.calculate_rewards_with_fee(&existing_stake);
+                    self.consume_reward_pool(net_reward.saturating_add(fee_amount))?;
+                    if self.get_auto_compound(caller) {
+                        compounded = net_reward;
+                    } else {
+                        paid_out = net_reward;
+                    }
 
                     // Update total fees collected
                     if fee_amount > 0 {
@@ -350,7 +509,7 @@ mod w3pi_staking {
                     let new_amount = existing_stake.amount.saturating_add(amount);
 
                     // Add pending net rewards to stake amount (auto-compound)
-                    let new_amount_with_rewards = new_amount.saturating_add(net_reward);
+                    let new_amount_with_rewards = new_amount.saturating_add(compounded);
 
                     StakeInfo {
                         amount: new_amount_with_rewards,
@@ -360,6 +519,8 @@ mod w3pi_staking {
                         tier_at_stake: current_tier,
                     }
                 } else {
+                    self.add_staker(caller);
+
                     // Create new stake info
                     StakeInfo {
                         amount,
@@ -372,11 +533,23 @@ mod w3pi_staking {
 
                 // Update storage
                 self.stakes.insert(caller, &stake_info);
-                self.total_staked = self.total_staked.saturating_add(amount);
+                self.total_staked = self
+                    .total_staked
+                    .saturating_add(amount)
+                    .saturating_add(compounded);
 
                 // Transfer tokens from caller to contract
                 self.transfer_tokens_to_contract(caller, amount)?;
 
+                // Pay out pending rewards for users who opted out of compounding
+                if paid_out > 0 {
+                    self.transfer_tokens_from_contract(caller, paid_out)?;
+                    self.env().emit_event(RewardsClaimed {
+                        account: caller,
+                        amount: paid_out,
+                    });
+                }
+
                 // Emit event
                 self.env().emit_event(Staked {
                     account: caller,
@@ -388,6 +561,28 @@ mod w3pi_staking {
             })
         }
 
+        /// Choose whether pending rewards are compounded when staking more
+        /// When disabled, `stake` pays pending net rewards out instead. The
+        /// performance fee is charged the same way in both modes.
+        #[ink(message)]
+        pub fn set_auto_compound(&mut self, enabled: bool) -> Result<(), Error> {
+            non_reentrant!(self, {
+                let caller = self.env().caller();
+                self.auto_compound.insert(caller, &enabled);
+                self.env().emit_event(AutoCompoundUpdated {
+                    account: caller,
+                    enabled,
+                });
+                Ok(())
+            })
+        }
+
+        /// View function to get whether an account compounds rewards (default true)
+        #[ink(message)]
+        pub fn get_auto_compound(&self, account: AccountId) -> bool {
+            self.auto_compound.get(account).unwrap_or(true)
+        }
+
         /// Request to unstake tokens
         #[ink(message)]
         pub fn request_unstake(&mut self, amount: u128) -> Result<(), Error> {
@@ -398,27 +593,17 @@ mod w3pi_staking {
                 let current_time = self.env().block_timestamp();
 
                 if amount == 0 {
-                    return Err(Error::InvalidParameters);
+                    return Err(Error::InvalidParameter);
                 }
 
                 // Get stake info
-                let mut stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameters)?;
+                let mut stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameter)?;
 
                 // Check if sufficient stake
                 if stake_info.amount < amount {
                     return Err(Error::InsufficientBalance);
                 }
 
-                // Check if unstaking requests limit reached
-                let mut requests = self.unstaking_requests.get(caller).unwrap_or_default();
-                let requests_len = u32::try_from(requests.len()).map_err(|_| Error::InvalidParameters)?;
-                if requests_len >= MAX_UNSTAKING_REQUESTS {
-                    return Err(Error::InvalidParameters);
-                }
-
-                // Update stake amount
-                stake_info.amount = stake_info.amount.saturating_sub(amount);
-
                 // Create unstaking request
                 let available_at = current_time.saturating_add(stake_info.unstaking_period);
                 let request = UnstakingRequest {
@@ -428,13 +613,20 @@ mod w3pi_staking {
                     claimed: false,
                 };
 
+                // Queue it, failing if the unstaking requests limit is reached
+                let mut requests = self.unstaking_requests.get(caller).unwrap_or_default();
+                Self::queue_unstaking_request(&mut requests, request)?;
+
+                // Update stake amount
+                stake_info.amount = stake_info.amount.saturating_sub(amount);
+
                 // Update storage
-                requests.push(request);
                 self.unstaking_requests.insert(caller, &requests);
 
                 if stake_info.amount == 0 {
                     // Remove stake if amount is 0
                     self.stakes.remove(caller);
+                    self.remove_staker(caller);
                 } else {
                     // Update stake info
                     self.stakes.insert(caller, &stake_info);
@@ -453,6 +645,101 @@ mod w3pi_staking {
             })
         }
 
+        /// Unstake immediately, skipping the unstaking period for a penalty
+        /// Pending rewards are paid out first as in `claim_rewards` when the reward pool
+        /// covers them. Otherwise they are left unsettled so the exit still succeeds: a
+        /// partial exit can claim them later on the remaining stake, a full exit forfeits
+        /// them. The penalty applies to principal only, goes to the fee wallet and
+        /// counts toward collected fees; returns the net amount received.
+        #[ink(message)]
+        pub fn emergency_unstake(&mut self, amount: u128) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_not_paused()?;
+
+                let caller = self.env().caller();
+                let current_time = self.env().block_timestamp();
+
+                if amount == 0 {
+                    return Err(Error::InvalidParameter);
+                }
+
+                // Get stake info
+                let mut stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameter)?;
+
+                // Check if sufficient stake
+                if stake_info.amount < amount {
+                    return Err(Error::InsufficientBalance);
+                }
+
+                // Settle rewards accrued on the full stake before it shrinks
+                let (net_reward, fee_amount) = self.calculate_rewards_with_fee(&stake_info);
+                if net_reward > 0
+                    && Self::reward_pool_covers(self.reward_pool, net_reward, fee_amount)
+                {
+                    self.pay_rewards(
+                        caller,
+                        stake_info.clone(),
+                        net_reward,
+                        fee_amount,
+                        current_time,
+                    )?;
+                    stake_info.last_claim = current_time;
+                }
+
+                let penalty = amount
+                    .saturating_mul(self.early_exit_penalty_bp as u128)
+                    .checked_div(10_000)
+                    .unwrap_or(0);
+                let net_amount = amount.saturating_sub(penalty);
+
+                // Update stake amount
+                stake_info.amount = stake_info.amount.saturating_sub(amount);
+                if stake_info.amount == 0 {
+                    self.stakes.remove(caller);
+                    self.remove_staker(caller);
+                } else {
+                    self.stakes.insert(caller, &stake_info);
+                }
+
+                self.total_staked = self.total_staked.saturating_sub(amount);
+
+                // Transfer penalty to fee wallet and the rest to the user
+                if penalty > 0 {
+                    self.total_collected_fees = self.total_collected_fees.saturating_add(penalty);
+                    self.transfer_tokens_from_contract(self.fee_wallet, penalty)?;
+                }
+                self.transfer_tokens_from_contract(caller, net_amount)?;
+
+                self.env().emit_event(EmergencyUnstaked {
+                    account: caller,
+                    gross_amount: amount,
+                    penalty,
+                    net_amount,
+                });
+
+                Ok(net_amount)
+            })
+        }
+
+        /// Set the emergency unstake penalty in basis points (owner only)
+        #[ink(message)]
+        pub fn set_early_exit_penalty_bp(&mut self, penalty_bp: u32) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+                if penalty_bp > MAX_EARLY_EXIT_PENALTY_BP {
+                    return Err(Error::InvalidParameter);
+                }
+                self.early_exit_penalty_bp = penalty_bp;
+                Ok(())
+            })
+        }
+
+        /// View function to get the emergency unstake penalty in basis points
+        #[ink(message)]
+        pub fn get_early_exit_penalty_bp(&self) -> u32 {
+            self.early_exit_penalty_bp
+        }
+
         /// Claim unstaked tokens that have completed the unstaking period
         #[ink(message)]
         pub fn claim_unstaked(&mut self) -> Result<(), Error> {
@@ -466,7 +753,7 @@ mod w3pi_staking {
                 let mut requests = self.unstaking_requests.get(caller).unwrap_or_default();
 
                 if requests.is_empty() {
-                    return Err(Error::InvalidParameters);
+                    return Err(Error::InvalidParameter);
                 }
 
                 let mut total_to_claim: u128 = 0; // Explicitly define type as u128
@@ -482,11 +769,14 @@ mod w3pi_staking {
                 }
 
                 if !has_claimable {
-                    return Err(Error::InvalidParameters);
+                    return Err(Error::InvalidParameter);
                 }
 
+                // Drop claimed requests so their slots free up
+                requests.retain(|request| !request.claimed);
+
                 // Update storage
-                self.unstaking_requests.insert(caller, &requests);
+                self.store_unstaking_requests(caller, requests);
 
                 // Transfer tokens
                 self.transfer_tokens_from_contract(caller, total_to_claim)?;
@@ -501,6 +791,98 @@ mod w3pi_staking {
             })
         }
 
+        /// Claim a single matured unstaking request by its index
+        /// `index` refers to the list returned by `get_unstaking_requests`; the claimed
+        /// request is removed, so later requests shift down by one.
+        #[ink(message)]
+        pub fn claim_unstaked_request(&mut self, index: u32) -> Result<u128, Error> {
+            non_reentrant!(self, {
+                self.ensure_not_paused()?;
+
+                let caller = self.env().caller();
+                let current_time = self.env().block_timestamp();
+
+                let mut requests = self.unstaking_requests.get(caller).unwrap_or_default();
+                requests.retain(|request| !request.claimed);
+                let request = requests
+                    .get(index as usize)
+                    .ok_or(Error::InvalidParameter)?;
+
+                if current_time < request.available_at {
+                    return Err(Error::InvalidParameter);
+                }
+
+                let amount = request.amount;
+                requests.remove(index as usize);
+
+                // Update storage
+                self.store_unstaking_requests(caller, requests);
+
+                // Transfer tokens
+                self.transfer_tokens_from_contract(caller, amount)?;
+
+                // Emit event
+                self.env().emit_event(UnstakedClaimed {
+                    account: caller,
+                    amount,
+                });
+
+                Ok(amount)
+            })
+        }
+
+        /// View function to get the total of matured, unclaimed unstaking requests
+        #[ink(message)]
+        pub fn get_claimable_unstaked(&self, account: AccountId) -> u128 {
+            let current_time = self.env().block_timestamp();
+            self.unstaking_requests
+                .get(account)
+                .unwrap_or_default()
+                .iter()
+                .filter(|request| !request.claimed && current_time >= request.available_at)
+                .fold(0u128, |total, request| total.saturating_add(request.amount))
+        }
+
+        /// Drop claimed requests and append `request`, keeping the rest in order
+        /// Fails if `MAX_UNSTAKING_REQUESTS` requests are already outstanding.
+        pub fn queue_unstaking_request(
+            requests: &mut Vec<UnstakingRequest>,
+            request: UnstakingRequest,
+        ) -> Result<(), Error> {
+            requests.retain(|request| !request.claimed);
+            let requests_len =
+                u32::try_from(requests.len()).map_err(|_| Error::InvalidParameter)?;
+            if requests_len >= MAX_UNSTAKING_REQUESTS {
+                return Err(Error::InvalidParameter);
+            }
+            requests.push(request);
+            Ok(())
+        }
+
+        /// Whether `reward_pool` can pay `net_reward` plus its `fee_amount`
+        pub fn reward_pool_covers(reward_pool: u128, net_reward: u128, fee_amount: u128) -> bool {
+            net_reward.saturating_add(fee_amount) <= reward_pool
+        }
+
+        /// Remove already-claimed unstaking requests, returning how many were removed
+        #[ink(message)]
+        pub fn prune_claimed_requests(&mut self) -> Result<u32, Error> {
+            non_reentrant!(self, {
+                let caller = self.env().caller();
+
+                let mut requests = self.unstaking_requests.get(caller).unwrap_or_default();
+                let before = requests.len();
+                requests.retain(|request| !request.claimed);
+                let removed = before.saturating_sub(requests.len());
+
+                if removed > 0 {
+                    self.store_unstaking_requests(caller, requests);
+                }
+
+                u32::try_from(removed).map_err(|_| Error::InvalidParameter)
+            })
+        }
+
         /// Claim staking rewards without unstaking
         #[ink(message)]
         pub fn claim_rewards(&mut self) -> Result<(), Error> {
@@ -511,46 +893,90 @@ mod w3pi_staking {
                 let current_time = self.env().block_timestamp();
 
                 // Get stake info
-                let mut stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameters)?;
+                let stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameter)?;
 
                 // Calculate rewards and fee
                 let (net_reward, fee_amount) = self.calculate_rewards_with_fee(&stake_info);
 
                 if net_reward == 0 {
-                    return Err(Error::InvalidParameters);
+                    return Err(Error::InvalidParameter);
                 }
 
-                // Update last claim time
-                stake_info.last_claim = current_time;
-                self.stakes.insert(caller, &stake_info);
+                self.pay_rewards(caller, stake_info, net_reward, fee_amount, current_time)
+            })
+        }
 
-                // Update total fees collected
-                self.total_collected_fees = self.total_collected_fees.saturating_add(fee_amount);
+        /// Claim part of the accrued staking rewards
+        /// The fee is charged pro rata and `last_claim` advances by the share of
+        /// elapsed time the claimed amount represents, so the rest keeps accruing.
+        #[ink(message)]
+        pub fn claim_rewards_partial(&mut self, amount: u128) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_not_paused()?;
 
-                // Transfer net rewards to user
-                self.transfer_tokens_from_contract(caller, net_reward)?;
+                let caller = self.env().caller();
+                let current_time = self.env().block_timestamp();
 
-                // Transfer fee to fee wallet (if fee is non-zero)
-                if fee_amount > 0 {
-                    self.transfer_tokens_from_contract(self.fee_wallet, fee_amount)?;
+                // Get stake info
+                let stake_info = self.stakes.get(caller).ok_or(Error::InvalidParameter)?;
 
-                    // Emit fee event
-                    self.env().emit_event(PerformanceFeeClaimed {
-                        account: caller,
+                // Calculate rewards and fee
+                let (net_reward, fee_amount) = self.calculate_rewards_with_fee(&stake_info);
+
+                if amount == 0 || amount > net_reward {
+                    return Err(Error::InvalidParameter);
+                }
+
+                // Claiming everything behaves exactly like claim_rewards
+                if amount == net_reward {
+                    return self.pay_rewards(
+                        caller,
+                        stake_info,
+                        net_reward,
                         fee_amount,
-                    });
+                        current_time,
+                    );
                 }
 
-                // Emit reward event
-                self.env().emit_event(RewardsClaimed {
-                    account: caller,
-                    amount: net_reward,
-                });
+                let time_elapsed = current_time.saturating_sub(stake_info.last_claim);
+                let (fee_portion, time_claimed) =
+                    Self::partial_claim_terms(amount, net_reward, fee_amount, time_elapsed)?;
+                let new_last_claim = stake_info.last_claim.saturating_add(time_claimed);
 
-                Ok(())
+                self.pay_rewards(caller, stake_info, amount, fee_portion, new_last_claim)
             })
         }
 
+        /// Fee and elapsed time charged for claiming `amount` out of `net_reward`
+        /// The time is rounded up so every partial claim moves `last_claim` forward;
+        /// a claim too small to consume any time would otherwise be repeatable forever.
+        /// Returns (fee_portion, time_claimed)
+        pub fn partial_claim_terms(
+            amount: u128,
+            net_reward: u128,
+            fee_amount: u128,
+            time_elapsed: u64,
+        ) -> Result<(u128, u64), Error> {
+            if amount == 0 || amount > net_reward {
+                return Err(Error::InvalidParameter);
+            }
+
+            let fee_portion = fee_amount
+                .saturating_mul(amount)
+                .checked_div(net_reward)
+                .unwrap_or(0);
+
+            let time_claimed = (time_elapsed as u128)
+                .checked_mul(amount)
+                .ok_or(Error::ArithmeticOverflow)?
+                .div_ceil(net_reward) as u64;
+            if time_claimed == 0 {
+                return Err(Error::InvalidParameter);
+            }
+
+            Ok((fee_portion, time_claimed))
+        }
+
         /// View function to get claimable rewards
         #[ink(message)]
         pub fn get_claimable_rewards(&self, account: AccountId) -> u128 {
@@ -562,6 +988,36 @@ mod w3pi_staking {
             }
         }
 
+        /// Fund the reward pool from the caller's tokens (owner only)
+        #[ink(message)]
+        pub fn fund_rewards(&mut self, amount: u128) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+
+                if amount == 0 {
+                    return Err(Error::InvalidParameter);
+                }
+
+                let caller = self.env().caller();
+                self.transfer_tokens_to_contract(caller, amount)?;
+                self.reward_pool = self.reward_pool.saturating_add(amount);
+
+                self.env().emit_event(RewardsFunded {
+                    funder: caller,
+                    amount,
+                    reward_pool: self.reward_pool,
+                });
+
+                Ok(())
+            })
+        }
+
+        /// View function to get tokens available for rewards
+        #[ink(message)]
+        pub fn get_reward_pool(&self) -> u128 {
+            self.reward_pool
+        }
+
         // Getter for total collected fees
         #[ink(message)]
         pub fn get_total_collected_fees(&self) -> u128 {
@@ -578,16 +1034,91 @@ mod w3pi_staking {
             })
         }
 
+        /// View function to get the annual reward rate for a tier
+        #[ink(message)]
+        pub fn get_apr_for_tier(&self, tier: Tier) -> u128 {
+            self.tier_apr.get(tier).unwrap_or(REWARDS_RATE_ANNUAL)
+        }
+
+        /// Set the annual reward rate for a tier (owner only)
+        #[ink(message)]
+        pub fn set_tier_apr(&mut self, tier: Tier, rate: u128) -> Result<(), Error> {
+            non_reentrant!(self, {
+                self.ensure_owner()?;
+                if rate > MAX_REWARDS_RATE_ANNUAL {
+                    return Err(Error::InvalidParameter);
+                }
+                let old_rate = self.get_apr_for_tier(tier);
+                self.tier_apr.insert(tier, &rate);
+                self.env().emit_event(TierAprUpdated {
+                    tier,
+                    old_rate,
+                    new_rate: rate,
+                });
+                Ok(())
+            })
+        }
+
+        /// View function to get the reward rate for a tier (alias of get_apr_for_tier)
+        #[ink(message)]
+        pub fn get_reward_rate(&self, tier: Tier) -> u128 {
+            self.get_apr_for_tier(tier)
+        }
+
+        /// Set the reward rate for a tier (alias of set_tier_apr, owner only)
+        #[ink(message)]
+        pub fn set_tier_reward_rate(&mut self, tier: Tier, rate: u128) -> Result<(), Error> {
+            self.set_tier_apr(tier, rate)
+        }
+
+        /// Annual reward rate a stake accrues at
+        /// The rate is read when rewards accrue, but for the tier recorded on the stake
+        /// (`tier_at_stake`, refreshed on every `stake`), not the registry's current tier,
+        /// so a tier shift does not change the APR of existing stakes.
+        pub fn get_stake_reward_rate(&self, stake: &StakeInfo) -> u128 {
+            self.get_apr_for_tier(stake.tier_at_stake)
+        }
+
         /// View function to get account stake info
         #[ink(message)]
         pub fn get_stake_info(&self, account: AccountId) -> Option<StakeInfo> {
             self.stakes.get(account)
         }
 
-        /// View function to get unstaking requests
+        /// View function to get outstanding (unclaimed) unstaking requests, oldest first
         #[ink(message)]
         pub fn get_unstaking_requests(&self, account: AccountId) -> Vec<UnstakingRequest> {
-            self.unstaking_requests.get(account).unwrap_or_default()
+            let mut requests = self.unstaking_requests.get(account).unwrap_or_default();
+            requests.retain(|request| !request.claimed);
+            requests
+        }
+
+        /// View function to get the number of accounts with an active stake
+        #[ink(message)]
+        pub fn get_staker_count(&self) -> u32 {
+            self.staker_count
+        }
+
+        /// View function to get (total staked, staker count, total collected fees)
+        #[ink(message)]
+        pub fn get_staking_stats(&self) -> (u128, u32, u128) {
+            (
+                self.total_staked,
+                self.staker_count,
+                self.total_collected_fees,
+            )
+        }
+
+        /// View function to get net rewards claimable across all stakers
+        #[ink(message)]
+        pub fn get_total_pending_rewards(&self) -> u128 {
+            self.stakers
+                .iter()
+                .filter_map(|account| self.stakes.get(account))
+                .fold(0u128, |total, stake_info| {
+                    let (net_reward, _) = self.calculate_rewards_with_fee(&stake_info);
+                    total.saturating_add(net_reward)
+                })
         }
 
         /// View function to get total staked amount
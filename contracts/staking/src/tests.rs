@@ -2,9 +2,14 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::w3pi_staking::W3piStaking;
+    use crate::unstaking_request::UnstakingRequest;
+    use crate::w3pi_staking::{
+        StakeInfo, W3piStaking, DEFAULT_EARLY_EXIT_PENALTY_BP, MAX_EARLY_EXIT_PENALTY_BP,
+        MAX_REWARDS_RATE_ANNUAL, MAX_UNSTAKING_REQUESTS, TIER1_REWARDS_RATE,
+    };
     use ink::env::{DefaultEnvironment, Environment};
     use shared::errors::Error;
+    use shared::tier::Tier;
 
     // Helper function to set up a test contract
     fn create_contract() -> W3piStaking {
@@ -21,6 +26,9 @@ mod tests {
         // Check initial state
         assert_eq!(contract.get_total_staked(), 0);
         assert_eq!(contract.get_total_collected_fees(), 0);
+        assert_eq!(contract.get_staker_count(), 0);
+        assert_eq!(contract.get_staking_stats(), (0, 0, 0));
+        assert_eq!(contract.get_total_pending_rewards(), 0);
     }
 
     // Test basic admin functions - not including pause/unpause
@@ -94,4 +102,211 @@ mod tests {
 
         assert!(unpause_result.is_ok(), "Owner should be able to unpause");
     }
+
+    #[ink::test]
+    fn test_tier_apr() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        // Defaults are populated at construction, ascending with tier
+        assert_eq!(contract.get_apr_for_tier(Tier::Tier1), TIER1_REWARDS_RATE);
+        assert!(contract.get_reward_rate(Tier::Tier4) > contract.get_reward_rate(Tier::Tier1));
+
+        // Owner can change a tier's rate
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_tier_apr(Tier::Tier2, 3_000_000_000).is_ok());
+        assert_eq!(contract.get_apr_for_tier(Tier::Tier2), 3_000_000_000);
+
+        // Rates above the cap are rejected
+        assert_eq!(
+            contract.set_tier_apr(Tier::Tier2, MAX_REWARDS_RATE_ANNUAL + 1),
+            Err(Error::InvalidParameter)
+        );
+
+        // Non-owners cannot change rates
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(
+            contract.set_tier_apr(Tier::Tier2, 1_000_000_000),
+            Err(Error::Unauthorized)
+        );
+    }
+
+    #[ink::test]
+    fn test_stake_accrues_at_rate_of_recorded_tier() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+        let stake = StakeInfo {
+            amount: 1_000,
+            staked_at: 0,
+            last_claim: 0,
+            unstaking_period: 0,
+            tier_at_stake: Tier::Tier1,
+        };
+        assert_eq!(contract.get_stake_reward_rate(&stake), TIER1_REWARDS_RATE);
+
+        // Rate changes apply to existing stakes of that tier...
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        contract
+            .set_tier_reward_rate(Tier::Tier1, 2_000_000_000)
+            .unwrap();
+        assert_eq!(contract.get_stake_reward_rate(&stake), 2_000_000_000);
+
+        // ...while other tiers' rates never reach a stake recorded at Tier1
+        contract
+            .set_tier_reward_rate(Tier::Tier4, 9_000_000_000)
+            .unwrap();
+        assert_eq!(contract.get_stake_reward_rate(&stake), 2_000_000_000);
+    }
+
+    #[ink::test]
+    fn test_fund_rewards_validation() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        // Pool starts empty
+        assert_eq!(contract.get_reward_pool(), 0);
+
+        // Zero funding is rejected
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.fund_rewards(0), Err(Error::InvalidParameter));
+
+        // Non-owners cannot fund the pool
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.fund_rewards(1_000), Err(Error::Unauthorized));
+        assert_eq!(contract.get_reward_pool(), 0);
+    }
+
+    #[ink::test]
+    fn test_early_exit_penalty_config() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        assert_eq!(
+            contract.get_early_exit_penalty_bp(),
+            DEFAULT_EARLY_EXIT_PENALTY_BP
+        );
+
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        assert!(contract.set_early_exit_penalty_bp(500).is_ok());
+        assert_eq!(contract.get_early_exit_penalty_bp(), 500);
+        assert_eq!(
+            contract.set_early_exit_penalty_bp(MAX_EARLY_EXIT_PENALTY_BP + 1),
+            Err(Error::InvalidParameter)
+        );
+
+        // Emergency unstake without a stake is rejected
+        assert_eq!(
+            contract.emergency_unstake(100),
+            Err(Error::InvalidParameter)
+        );
+    }
+
+    #[test]
+    fn test_emergency_unstake_skips_rewards_an_empty_pool_cannot_cover() {
+        // An empty pool leaves rewards unsettled instead of blocking the exit
+        assert!(!W3piStaking::reward_pool_covers(0, 90, 10));
+        assert!(!W3piStaking::reward_pool_covers(99, 90, 10));
+        assert!(W3piStaking::reward_pool_covers(100, 90, 10));
+        assert!(!W3piStaking::reward_pool_covers(
+            u128::MAX - 1,
+            u128::MAX,
+            1
+        ));
+    }
+
+    #[ink::test]
+    fn test_auto_compound_preference() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        // Compounding is on by default
+        assert!(contract.get_auto_compound(accounts.eve));
+
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.eve);
+        assert!(contract.set_auto_compound(false).is_ok());
+        assert!(!contract.get_auto_compound(accounts.eve));
+
+        // Other accounts are unaffected
+        assert!(contract.get_auto_compound(accounts.frank));
+    }
+
+    #[ink::test]
+    fn test_prune_claimed_requests_without_requests() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.eve);
+        assert_eq!(contract.prune_claimed_requests(), Ok(0));
+        assert!(contract.get_unstaking_requests(accounts.eve).is_empty());
+    }
+
+    #[test]
+    fn test_more_than_max_lifetime_unstaking_requests() {
+        let request = |amount: u128| UnstakingRequest {
+            amount,
+            requested_at: 0,
+            available_at: 0,
+            claimed: false,
+        };
+        let mut requests = Vec::new();
+
+        // Nearly twice the cap over the account's lifetime, claiming every other request
+        for amount in 1..(2 * MAX_UNSTAKING_REQUESTS as u128) {
+            assert_eq!(
+                W3piStaking::queue_unstaking_request(&mut requests, request(amount)),
+                Ok(())
+            );
+            if amount % 2 == 0 {
+                requests.last_mut().unwrap().claimed = true;
+            }
+        }
+
+        // Claimed entries were pruned and the outstanding ones kept their order
+        let amounts: Vec<u128> = requests.iter().map(|request| request.amount).collect();
+        assert_eq!(amounts.len(), MAX_UNSTAKING_REQUESTS as usize);
+        assert_eq!(&amounts[..3], &[1, 3, 5]);
+        assert_eq!(
+            W3piStaking::queue_unstaking_request(&mut requests, request(99)),
+            Err(Error::InvalidParameter)
+        );
+    }
+
+    #[ink::test]
+    fn test_claim_unstaked_request_invalid_index() {
+        let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+        let mut contract = create_contract();
+
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.eve);
+        assert_eq!(contract.get_claimable_unstaked(accounts.eve), 0);
+        assert_eq!(
+            contract.claim_unstaked_request(0),
+            Err(Error::InvalidParameter)
+        );
+    }
+
+    #[test]
+    fn test_partial_claims_never_exceed_full_claim() {
+        // Linear accrual: 100 reward units per ms, fee-free for clarity
+        let rate = 100u128;
+        let elapsed = 10u64;
+        let full_claim = rate * elapsed as u128;
+
+        for first_claim in [1u128, 50, 99, 150, 999] {
+            let (_, time_claimed) =
+                W3piStaking::partial_claim_terms(first_claim, full_claim, 0, elapsed).unwrap();
+            assert!(time_claimed > 0);
+
+            // Second claim takes everything accrued over the unclaimed time
+            let second_claim = rate * elapsed.saturating_sub(time_claimed) as u128;
+            assert!(first_claim + second_claim <= full_claim);
+        }
+    }
+
+    #[test]
+    fn test_partial_claim_without_elapsed_time_rejected() {
+        assert_eq!(
+            W3piStaking::partial_claim_terms(10, 1_000, 0, 0),
+            Err(Error::InvalidParameter)
+        );
+    }
 }
@@ -23,9 +23,14 @@ pub use traits::{PSP22Burnable, PSP22Metadata, PSP22Mintable, PSP22};
 //
 // It is a good practice to also implement the optional PSP22Metadata extension (5)
 // and include unit tests (6).
+//
+// W3PI supply is created and destroyed by the portfolio contract, so the
+// PSP22Mintable and PSP22Burnable extensions are restricted to a single minter (7).
 #[ink::contract]
 mod token {
-    use crate::{PSP22Data, PSP22Error, PSP22Event, PSP22Metadata, PSP22};
+    use crate::{
+        PSP22Burnable, PSP22Data, PSP22Error, PSP22Event, PSP22Metadata, PSP22Mintable, PSP22,
+    };
     use ink::prelude::{string::String, vec::Vec};
 
     #[ink(storage)]
@@ -34,6 +39,8 @@ mod token {
         name: Option<String>,
         symbol: Option<String>,
         decimals: u8,
+        owner: AccountId,
+        minter: Option<AccountId>,
     }
 
     impl Token {
@@ -44,17 +51,46 @@ mod token {
             symbol: Option<String>,
             decimals: u8,
         ) -> Self {
-            let (data, events) = PSP22Data::new(supply, Self::env().caller()); // (2)
+            let caller = Self::env().caller();
+            let (data, events) = PSP22Data::new(supply, caller); // (2)
             let contract = Self {
                 data,
                 name,
                 symbol,
                 decimals,
+                owner: caller,
+                minter: None,
             };
             contract.emit_events(events);
             contract
         }
 
+        /// Set the account allowed to mint and burn, normally the portfolio contract (owner only)
+        #[ink(message)]
+        pub fn set_minter(&mut self, minter: Option<AccountId>) -> Result<(), PSP22Error> {
+            if self.env().caller() != self.owner {
+                return Err(PSP22Error::Custom(String::from("Caller is not the owner")));
+            }
+            self.minter = minter;
+            Ok(())
+        }
+
+        /// Get the account allowed to mint and burn
+        #[ink(message)]
+        pub fn get_minter(&self) -> Option<AccountId> {
+            self.minter
+        }
+
+        // Only the minter may change the supply.
+        // (7)
+        fn ensure_minter(&self) -> Result<AccountId, PSP22Error> {
+            let caller = self.env().caller();
+            if self.minter != Some(caller) {
+                return Err(PSP22Error::Custom(String::from("Caller is not the minter")));
+            }
+            Ok(caller)
+        }
+
         // A helper function emitting events contained in a vector of PSP22Events.
         // (4)
         fn emit_events(&self, events: Vec<PSP22Event>) {
@@ -161,10 +197,66 @@ mod token {
         }
     }
 
+    // (7)
+    impl PSP22Mintable for Token {
+        #[ink(message)]
+        fn mint(&mut self, value: u128) -> Result<(), PSP22Error> {
+            let minter = self.ensure_minter()?;
+            let events = self.data.mint(minter, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
+    impl PSP22Burnable for Token {
+        #[ink(message)]
+        fn burn(&mut self, value: u128) -> Result<(), PSP22Error> {
+            let minter = self.ensure_minter()?;
+            let events = self.data.burn(minter, value)?;
+            self.emit_events(events);
+            Ok(())
+        }
+    }
+
     // (6)
     #[cfg(test)]
     mod tests {
         use super::Token;
         crate::tests!(Token, (|supply| Token::new(supply, None, None, 0)));
+
+        use crate::{PSP22Burnable, PSP22Error, PSP22Mintable, PSP22};
+        use ink::env::{test, DefaultEnvironment};
+
+        #[ink::test]
+        fn only_minter_can_mint_and_burn() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut token = Token::new(100, None, None, 0);
+            let not_minter = Err(PSP22Error::Custom("Caller is not the minter".into()));
+
+            assert_eq!(token.mint(10), not_minter);
+            assert_eq!(token.set_minter(Some(accounts.bob)), Ok(()));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(token.mint(10), Ok(()));
+            assert_eq!(token.balance_of(accounts.bob), 10);
+            assert_eq!(token.burn(4), Ok(()));
+            assert_eq!(token.total_supply(), 106);
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(token.burn(1), not_minter);
+        }
+
+        #[ink::test]
+        fn only_owner_sets_minter() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut token = Token::new(0, None, None, 0);
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                token.set_minter(Some(accounts.bob)),
+                Err(PSP22Error::Custom("Caller is not the owner".into()))
+            );
+            assert_eq!(token.get_minter(), None);
+        }
     }
 }